@@ -0,0 +1,301 @@
+//! Snapshot and restore of the local data directory (`~/.remote-dev`).
+//!
+//! An archive is a gzipped tarball holding `manifest.json` plus:
+//!
+//! - `sqlite.db` and `analytics/analytics.db`, copied with SQLite's online
+//!   backup (`sqlite3 .backup`) so a running server can't tear them
+//! - `profiles/` (agent knowledge files) and `recordings/` (transcripts)
+//!
+//! Worktrees and cloned repos are left out: git already owns them.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::process::{self, RunOptions};
+
+const MANIFEST: &str = "manifest.json";
+const FORMAT_VERSION: u32 = 1;
+const DATABASES: [&str; 2] = ["sqlite.db", "analytics/analytics.db"];
+const DIRECTORIES: [&str; 2] = ["profiles", "recordings"];
+/// Files SQLite keeps beside a database in WAL mode. A stale WAL left next to
+/// a restored database would be replayed onto it.
+const SIDECARS: [&str; 2] = ["-wal", "-shm"];
+/// Generous: recordings can make the tarball large.
+const STEP_TIMEOUT: Duration = Duration::from_secs(600);
+
+type BoxError = Box<dyn std::error::Error>;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub format_version: u32,
+    pub created_at: String,
+    pub rdv_version: String,
+    /// Rows in drizzle's `__drizzle_migrations` when the snapshot was taken.
+    pub schema_migrations: Option<u32>,
+    /// Archived paths, relative to the data directory.
+    pub contents: Vec<String>,
+}
+
+async fn run(program: &str, args: &[&str]) -> Result<String, BoxError> {
+    Ok(process::run(program, args, None, RunOptions::LOCAL.timeout(STEP_TIMEOUT)).await?)
+}
+
+/// Applied migration count, or `None` when the database has no drizzle table.
+async fn schema_migrations(db: &Path) -> Option<u32> {
+    if !db.is_file() {
+        return None;
+    }
+    let db = db.to_string_lossy();
+    run("sqlite3", &[&db, "SELECT count(*) FROM __drizzle_migrations"])
+        .await
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Refuse a snapshot from a newer schema than the live database: the
+/// installed server has no migrations to make sense of it. Older snapshots
+/// are fine; the server migrates them forward on start.
+fn check_schema(snapshot: Option<u32>, live: Option<u32>) -> Result<(), String> {
+    match (snapshot, live) {
+        (Some(s), Some(l)) if s > l => Err(format!(
+            "snapshot has {s} schema migrations but this install has {l}; upgrade Remote Dev before restoring (or pass --force)"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Quote a path for a sqlite3 shell dot-command. Single quotes can't hold a
+/// `'`; double quotes take C-style backslash escapes.
+fn dot_command_arg(path: &Path) -> String {
+    let escaped = path.to_string_lossy().replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write a snapshot of `data_dir` to the gzipped tarball `dest`.
+pub async fn create(data_dir: &Path, dest: &Path) -> Result<Manifest, BoxError> {
+    let staging = data_dir.join(format!(".backup-staging-{}", std::process::id()));
+    let result = stage(data_dir, &staging).await;
+    let result = match result {
+        Ok(manifest) => {
+            let staging_str = staging.to_string_lossy();
+            let dest_str = dest.to_string_lossy();
+            run("tar", &["-czf", &dest_str, "-C", &staging_str, "."])
+                .await
+                .map(|_| manifest)
+        }
+        Err(e) => Err(e),
+    };
+    fs::remove_dir_all(&staging).ok();
+    result
+}
+
+async fn stage(data_dir: &Path, staging: &Path) -> Result<Manifest, BoxError> {
+    fs::create_dir_all(staging)?;
+    let mut contents = Vec::new();
+
+    for db in DATABASES {
+        let src = data_dir.join(db);
+        if !src.is_file() {
+            continue;
+        }
+        let dst = staging.join(db);
+        if let Some(dir) = dst.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let src_str = src.to_string_lossy();
+        run("sqlite3", &[&src_str, &format!(".backup {}", dot_command_arg(&dst))]).await?;
+        contents.push(db.to_string());
+    }
+
+    for dir in DIRECTORIES {
+        let src = data_dir.join(dir);
+        if src.is_dir() {
+            copy_dir(&src, &staging.join(dir))?;
+            contents.push(dir.to_string());
+        }
+    }
+
+    let manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        created_at: Utc::now().to_rfc3339(),
+        rdv_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_migrations: schema_migrations(&staging.join("sqlite.db")).await,
+        contents,
+    };
+    fs::write(staging.join(MANIFEST), serde_json::to_string_pretty(&manifest)?)?;
+    Ok(manifest)
+}
+
+/// Restore `archive` into `data_dir`. Each replaced path is kept beside the
+/// original as `<name>.pre-restore-<stamp>`.
+pub async fn restore(archive: &Path, data_dir: &Path, force: bool) -> Result<(Manifest, Vec<PathBuf>), BoxError> {
+    let staging = data_dir.join(format!(".restore-staging-{}", std::process::id()));
+    fs::create_dir_all(&staging)?;
+    let result = unpack_and_swap(archive, data_dir, &staging, force).await;
+    fs::remove_dir_all(&staging).ok();
+    result
+}
+
+async fn unpack_and_swap(
+    archive: &Path,
+    data_dir: &Path,
+    staging: &Path,
+    force: bool,
+) -> Result<(Manifest, Vec<PathBuf>), BoxError> {
+    let archive_str = archive.to_string_lossy();
+    let staging_str = staging.to_string_lossy();
+    run("tar", &["-xzf", &archive_str, "-C", &staging_str]).await?;
+
+    let manifest: Manifest = serde_json::from_str(
+        &fs::read_to_string(staging.join(MANIFEST)).map_err(|_| "archive has no manifest.json; not an rdv backup")?,
+    )?;
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(format!(
+            "backup format {} is newer than this rdv understands ({FORMAT_VERSION})",
+            manifest.format_version
+        )
+        .into());
+    }
+    if let Some(item) = manifest
+        .contents
+        .iter()
+        .find(|c| !DATABASES.contains(&c.as_str()) && !DIRECTORIES.contains(&c.as_str()))
+    {
+        return Err(format!("backup manifest lists unexpected path {item:?}").into());
+    }
+    if !force {
+        check_schema(manifest.schema_migrations, schema_migrations(&data_dir.join("sqlite.db")).await)?;
+    }
+
+    // Check everything is there before any live data moves.
+    if let Some(item) = manifest.contents.iter().find(|c| !staging.join(c).exists()) {
+        return Err(format!("archive is missing {item}, which its manifest lists").into());
+    }
+
+    let stamp = Utc::now().format("%Y%m%d%H%M%S").to_string();
+    let kept = swap(data_dir, staging, &manifest.contents, &stamp)?;
+    Ok((manifest, kept))
+}
+
+/// Move each live item, and a database's WAL sidecars, aside as
+/// `<name>.pre-restore-<stamp>`, then move the staged copy into its place.
+/// Returns the moved-aside paths. On failure the renames done so far are
+/// undone, so live data is either fully replaced or left as it was.
+fn swap(data_dir: &Path, staging: &Path, items: &[String], stamp: &str) -> Result<Vec<PathBuf>, BoxError> {
+    let mut renamed: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut rename = |from: PathBuf, to: PathBuf| -> io::Result<()> {
+        fs::rename(&from, &to)?;
+        renamed.push((from, to));
+        Ok(())
+    };
+    let result = items.iter().try_for_each(|item| {
+        let live = data_dir.join(item);
+        let mut outgoing = vec![live.clone()];
+        if DATABASES.contains(&item.as_str()) {
+            outgoing.extend(SIDECARS.iter().map(|s| PathBuf::from(format!("{}{s}", live.display()))));
+        }
+        for path in outgoing.into_iter().filter(|p| p.exists()) {
+            let aside = PathBuf::from(format!("{}.pre-restore-{stamp}", path.display()));
+            rename(path, aside)?;
+        }
+        if let Some(dir) = live.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        rename(staging.join(item), live)
+    });
+
+    match result {
+        Ok(()) => Ok(renamed
+            .into_iter()
+            .filter(|(from, _)| !from.starts_with(staging))
+            .map(|(_, aside)| aside)
+            .collect()),
+        Err(e) => {
+            let stuck: Vec<String> = renamed
+                .into_iter()
+                .rev()
+                .filter(|(from, to)| fs::rename(to, from).is_err())
+                .map(|(from, to)| format!("{} (belongs at {})", to.display(), from.display()))
+                .collect();
+            if stuck.is_empty() {
+                Err(format!("restore failed, live data left as it was: {e}").into())
+            } else {
+                Err(format!("restore failed: {e}; could not move back: {}", stuck.join(", ")).into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use super::{check_schema, dot_command_arg, swap};
+
+    #[test]
+    fn older_or_unknown_schemas_restore() {
+        assert!(check_schema(Some(20), Some(28)).is_ok());
+        assert!(check_schema(Some(28), Some(28)).is_ok());
+        assert!(check_schema(None, Some(28)).is_ok());
+        assert!(check_schema(Some(28), None).is_ok());
+    }
+
+    #[test]
+    fn newer_schema_is_refused() {
+        let err = check_schema(Some(30), Some(28)).unwrap_err();
+        assert!(err.contains("upgrade Remote Dev"));
+    }
+
+    #[test]
+    fn dot_command_paths_survive_quotes() {
+        assert_eq!(dot_command_arg(Path::new("/tmp/it's/a.db")), r#""/tmp/it's/a.db""#);
+        assert_eq!(dot_command_arg(Path::new(r#"/tmp/"q"\b.db"#)), r#""/tmp/\"q\"\\b.db""#);
+    }
+
+    #[test]
+    fn swap_moves_wal_aside_and_rolls_back_on_failure() {
+        let root = std::env::temp_dir().join(format!("rdv-restore-{}", std::process::id()));
+        let (data, staging) = (root.join("data"), root.join("staging"));
+        fs::create_dir_all(&data).unwrap();
+        fs::create_dir_all(staging.join("profiles")).unwrap();
+        fs::write(data.join("sqlite.db"), "live").unwrap();
+        fs::write(data.join("sqlite.db-wal"), "stale").unwrap();
+        fs::write(staging.join("sqlite.db"), "restored").unwrap();
+
+        // "recordings" is not staged: the db swap must be undone.
+        let items = ["sqlite.db".to_string(), "recordings".to_string()];
+        assert!(swap(&data, &staging, &items, "1").is_err());
+        assert_eq!(fs::read_to_string(data.join("sqlite.db")).unwrap(), "live");
+        assert!(data.join("sqlite.db-wal").exists());
+        assert_eq!(fs::read_to_string(staging.join("sqlite.db")).unwrap(), "restored");
+
+        let kept = swap(&data, &staging, &items[..1], "2").unwrap();
+        assert_eq!(fs::read_to_string(data.join("sqlite.db")).unwrap(), "restored");
+        assert!(!data.join("sqlite.db-wal").exists());
+        assert_eq!(kept, [data.join("sqlite.db.pre-restore-2"), data.join("sqlite.db-wal.pre-restore-2")]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
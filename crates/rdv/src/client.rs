@@ -1,7 +1,22 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use crate::config::{ConnectionMethod, ServerConfig};
+use crate::error::{ApiError, HttpFailure};
+use crate::queue::{Journal, QueuedCall};
+use crate::trace::{random_u64, Trace};
+
+/// How many times a request is retried after a `429 Too Many Requests`.
+const RATE_LIMIT_MAX_RETRIES: u32 = 3;
+/// Upper bound on a single `Retry-After` wait, so a misbehaving server can't
+/// park the CLI (and the hook that invoked it) for minutes.
+const RATE_LIMIT_MAX_WAIT_SECS: u64 = 30;
+/// How many times an idempotent POST is resent after a connection failure
+/// or 5xx.
+const IDEMPOTENT_MAX_RETRIES: u32 = 2;
 
 /// Dual-client wrapper that routes requests to the correct server.
 ///
@@ -15,6 +30,8 @@ pub struct Client {
     terminal_base_url: String,
     api_key: Option<String>,
     session_id: Option<String>,
+    offline_queue: bool,
+    trace: Trace,
 }
 
 fn build_client(method: &ConnectionMethod) -> reqwest::Client {
@@ -36,6 +53,8 @@ impl Client {
             terminal_base_url: cfg.terminal_base_url(),
             api_key: cfg.api_key.clone(),
             session_id: cfg.session_id.clone(),
+            offline_queue: cfg.offline_queue,
+            trace: Trace::new(cfg.trace),
         }
     }
 
@@ -53,7 +72,11 @@ impl Client {
             (&self.api_client, &self.api_base_url)
         };
         let url = format!("{base}{path}");
-        let builder = client.request(method, &url);
+        let span_id = self.trace.span_id();
+        let builder = client
+            .request(method, &url)
+            .header("x-request-id", &span_id)
+            .header("traceparent", self.trace.traceparent(&span_id));
         if !is_internal {
             if let Some(ref key) = self.api_key {
                 return builder.header("authorization", format!("Bearer {key}"));
@@ -62,13 +85,62 @@ impl Client {
         builder
     }
 
+    /// Send a request, backing off and retrying when the server answers
+    /// `429 Too Many Requests`.
+    ///
+    /// The server throttles per token; honouring `Retry-After` keeps a burst of
+    /// hook invocations from failing outright. Requests whose body can't be
+    /// cloned (streams) are sent once and the 429 is surfaced to the caller.
+    async fn send(&self, builder: reqwest::RequestBuilder) -> Result<reqwest::Response, ApiError> {
+        let mut pending = builder;
+        let mut attempt = 0;
+        loop {
+            let retry = pending.try_clone();
+            let resp = self.send_once(pending).await?;
+            let Some(next) = retry else {
+                return Ok(resp);
+            };
+            if resp.status() != reqwest::StatusCode::TOO_MANY_REQUESTS
+                || attempt >= RATE_LIMIT_MAX_RETRIES
+            {
+                return Ok(resp);
+            }
+            let wait = retry_after(resp.headers()).unwrap_or_else(|| backoff(attempt));
+            tokio::time::sleep(wait).await;
+            pending = next;
+            attempt += 1;
+        }
+    }
+
+    /// Send one attempt, logging it when `RDV_TRACE` is set.
+    async fn send_once(&self, builder: reqwest::RequestBuilder) -> Result<reqwest::Response, ApiError> {
+        if !self.trace.verbose {
+            return builder.send().await.map_err(ApiError::from_reqwest);
+        }
+        let (client, request) = builder.build_split();
+        let request = request.map_err(ApiError::from_reqwest)?;
+        let method = request.method().to_string();
+        let path = request.url().path().to_string();
+        let request_id = request
+            .headers()
+            .get("x-request-id")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string();
+        let started = Instant::now();
+        let result = client.execute(request).await;
+        let status = result.as_ref().ok().map(|r| r.status().as_u16());
+        self.trace.log(&method, &path, status, started.elapsed(), &request_id);
+        result.map_err(ApiError::from_reqwest)
+    }
+
     // ── generic verbs ────────────────────────────────────────────────
 
     pub async fn get<T: DeserializeOwned>(
         &self,
         path: &str,
     ) -> Result<T, Box<dyn std::error::Error>> {
-        let resp = self.request(reqwest::Method::GET, path).send().await?;
+        let resp = self.send(self.request(reqwest::Method::GET, path)).await?;
         handle_response(resp).await
     }
 
@@ -81,37 +153,45 @@ impl Client {
         T: DeserializeOwned,
         Q: Serialize + ?Sized,
     {
-        let resp = self
-            .request(reqwest::Method::GET, path)
-            .query(query)
-            .send()
-            .await?;
+        let req = self.request(reqwest::Method::GET, path).query(query);
+        let resp = self.send(req).await?;
         handle_response(resp).await
     }
 
     pub async fn get_bytes(&self, path: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-        let resp = self.request(reqwest::Method::GET, path).send().await?;
+        let resp = self.send(self.request(reqwest::Method::GET, path)).await?;
         if resp.status().is_success() {
             Ok(resp.bytes().await?.to_vec())
         } else {
-            Err(format_http_error(resp).await.into())
+            Err(api_error(resp).await.into())
         }
     }
 
     pub async fn get_text(&self, path: &str) -> Result<String, Box<dyn std::error::Error>> {
-        let resp = self.request(reqwest::Method::GET, path).send().await?;
+        let resp = self.send(self.request(reqwest::Method::GET, path)).await?;
         if resp.status().is_success() {
-            Ok(resp.text().await?)
+            Ok(resp.text().await.map_err(ApiError::from_reqwest)?)
         } else {
-            Err(format_http_error(resp).await.into())
+            Err(api_error(resp).await.into())
         }
     }
 
+    /// GET returning the status code and body without treating non-2xx as an
+    /// error, for probes where a 503 body carries the diagnosis.
+    pub async fn get_raw(
+        &self,
+        path: &str,
+    ) -> Result<(reqwest::StatusCode, String), Box<dyn std::error::Error>> {
+        let resp = self.send(self.request(reqwest::Method::GET, path)).await?;
+        let status = resp.status();
+        Ok((status, resp.text().await.map_err(ApiError::from_reqwest)?))
+    }
+
     pub async fn post_empty(
         &self,
         path: &str,
     ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
-        let resp = self.request(reqwest::Method::POST, path).send().await?;
+        let resp = self.send(self.request(reqwest::Method::POST, path)).await?;
         handle_response(resp).await
     }
 
@@ -123,11 +203,8 @@ impl Client {
     where
         Q: Serialize + ?Sized,
     {
-        let resp = self
-            .request(reqwest::Method::POST, path)
-            .query(query)
-            .send()
-            .await?;
+        let req = self.request(reqwest::Method::POST, path).query(query);
+        let resp = self.send(req).await?;
         handle_response(resp).await
     }
 
@@ -136,11 +213,8 @@ impl Client {
         T: DeserializeOwned,
         B: Serialize + ?Sized,
     {
-        let resp = self
-            .request(reqwest::Method::PATCH, path)
-            .json(body)
-            .send()
-            .await?;
+        let req = self.request(reqwest::Method::PATCH, path).json(body);
+        let resp = self.send(req).await?;
         handle_response(resp).await
     }
 
@@ -148,7 +222,7 @@ impl Client {
         &self,
         path: &str,
     ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
-        let resp = self.request(reqwest::Method::DELETE, path).send().await?;
+        let resp = self.send(self.request(reqwest::Method::DELETE, path)).await?;
         handle_response(resp).await
     }
 
@@ -157,55 +231,269 @@ impl Client {
         path: &str,
         body: &B,
     ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
-        let resp = self
-            .request(reqwest::Method::DELETE, path)
-            .json(body)
-            .send()
-            .await?;
+        let req = self.request(reqwest::Method::DELETE, path).json(body);
+        let resp = self.send(req).await?;
         handle_response(resp).await
     }
 
+    /// Fetch one page of a keyset-paginated list endpoint.
+    ///
+    /// List routes return `{ "<key>": [...], "nextCursor": "..." }`; `limit`
+    /// and `cursor` are forwarded as query parameters alongside `query`.
+    pub async fn get_page<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        key: &str,
+        query: &[(&str, String)],
+        limit: Option<u32>,
+        cursor: Option<&str>,
+    ) -> Result<Page<T>, Box<dyn std::error::Error>> {
+        let mut query = query.to_vec();
+        if let Some(l) = limit {
+            query.push(("limit", l.to_string()));
+        }
+        if let Some(c) = cursor {
+            query.push(("cursor", c.to_string()));
+        }
+        let value: serde_json::Value = self.get_with_query(path, &query).await?;
+        split_page(value, key)
+    }
+
+    /// Follow `nextCursor` until the list is exhausted. Servers that don't
+    /// paginate simply omit the cursor, so this is one request against them.
+    /// A cursor seen before means the server is looping, which is an error
+    /// rather than a silently truncated or endless list.
+    pub async fn get_all_pages<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        key: &str,
+        query: &[(&str, String)],
+    ) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+        let mut items = Vec::new();
+        let mut seen = HashSet::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page: Page<T> = self
+                .get_page(path, key, query, None, cursor.as_deref())
+                .await?;
+            items.extend(page.items);
+            match page.next_cursor {
+                Some(next) if !seen.insert(next.clone()) => {
+                    return Err(format!("{path} repeated page cursor {next:?}; stopping").into());
+                }
+                Some(next) => cursor = Some(next),
+                None => return Ok(items),
+            }
+        }
+    }
+
     /// POST with a raw JSON value body, returning raw JSON.
     pub async fn post_json(
         &self,
         path: &str,
         body: &serde_json::Value,
     ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
-        let resp = self
-            .request(reqwest::Method::POST, path)
-            .json(body)
-            .send()
-            .await?;
+        let req = self.request(reqwest::Method::POST, path).json(body);
+        let resp = self.send(req).await?;
         handle_response(resp).await
     }
 
-    /// POST reading body bytes from stdin (for `task sync`).
-    pub async fn post_raw_bytes(
+    /// POST that creates something and is safe to resend. It carries an
+    /// `Idempotency-Key` (the caller's, or a fresh one), so the server answers
+    /// a repeat with the first result instead of creating a duplicate.
+    /// Connection failures and 5xx answers are retried with the same key.
+    ///
+    /// A timed-out request may already have run, so the route must honour the
+    /// key; `POST /api/sessions` does, through its scope-key dedup.
+    pub async fn post_json_idempotent(
         &self,
         path: &str,
-        bytes: Vec<u8>,
+        body: &serde_json::Value,
+        key: Option<&str>,
     ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
-        let resp = self
-            .request(reqwest::Method::POST, path)
-            .header("content-type", "application/json")
-            .body(bytes)
-            .send()
-            .await?;
-        handle_response(resp).await
+        let key = key.map_or_else(new_idempotency_key, str::to_string);
+        let mut attempt = 0;
+        loop {
+            let req = self
+                .request(reqwest::Method::POST, path)
+                .header("idempotency-key", &key)
+                .json(body);
+            let err = match self.send(req).await {
+                Ok(resp) if resp.status().is_success() => return handle_response(resp).await,
+                Ok(resp) => api_error(resp).await,
+                Err(e) => e,
+            };
+            // 429s were already retried by `send`.
+            let transient = matches!(err, ApiError::ConnectionFailed(_) | ApiError::ServerError(_));
+            if !transient || attempt >= IDEMPOTENT_MAX_RETRIES {
+                return Err(err.into());
+            }
+            tokio::time::sleep(backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// POST that may be delivered late: with `RDV_OFFLINE_QUEUE` enabled, an
+    /// unreachable server journals the call and returns `{"queued": true}`
+    /// instead of failing. Earlier queued calls are replayed first so the
+    /// server sees them in order; if any are still stuck, this call joins the
+    /// end of the queue rather than overtaking them.
+    pub async fn post_json_queued(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        if !self.offline_queue {
+            return self.post_json(path, body).await;
+        }
+        // One lock across the check, replay and append, so two processes
+        // can't both see an empty journal and then interleave behind it.
+        let journal = Journal::open_default();
+        let lock = journal.lock()?;
+        let queue_behind = !journal.load()?.is_empty() && self.replay(&journal).await?.remaining > 0;
+        let result = if queue_behind {
+            None
+        } else {
+            match self.post_json(path, body).await {
+                Err(e) if is_unreachable(e.as_ref()) => None,
+                other => Some(other),
+            }
+        };
+        if let Some(result) = result {
+            return result;
+        }
+        journal.append(&lock, &QueuedCall {
+            method: "POST".into(),
+            path: path.into(),
+            body: body.clone(),
+            queued_at: chrono::Utc::now().to_rfc3339(),
+        })?;
+        if queue_behind {
+            eprintln!("earlier calls still queued; queued POST {path} behind them");
+        } else {
+            eprintln!("server unreachable; queued POST {path} for later delivery");
+        }
+        Ok(serde_json::json!({ "queued": true, "path": path }))
+    }
+
+    /// Replay the offline journal in order. Stops at the first call that
+    /// still can't reach the server (or gets a 429 or 5xx) and keeps it and
+    /// everything after it; calls rejected with another 4xx are dropped and
+    /// reported, since replaying them again won't help. Holds the journal
+    /// lock throughout, so concurrent appends wait rather than get lost.
+    pub async fn flush_queue(&self) -> Result<FlushReport, Box<dyn std::error::Error>> {
+        let journal = Journal::open_default();
+        let _lock = journal.lock()?;
+        self.replay(&journal).await
+    }
+
+    /// The body of [`Client::flush_queue`]; the caller holds the journal lock.
+    async fn replay(&self, journal: &Journal) -> Result<FlushReport, Box<dyn std::error::Error>> {
+        let calls = journal.load()?;
+        let mut report = FlushReport::default();
+        let mut stopped_at = calls.len();
+        for (i, call) in calls.iter().enumerate() {
+            let method = reqwest::Method::from_bytes(call.method.as_bytes())?;
+            let req = self.request(method, &call.path).json(&call.body);
+            match self.send(req).await {
+                Ok(resp) if resp.status().is_success() => report.replayed += 1,
+                Ok(resp)
+                    if resp.status().is_client_error()
+                        && resp.status() != reqwest::StatusCode::TOO_MANY_REQUESTS =>
+                {
+                    report
+                        .dropped
+                        .push(format!("{} {}: {}", call.method, call.path, api_error(resp).await.summary()))
+                }
+                _ => {
+                    stopped_at = i;
+                    break;
+                }
+            }
+        }
+        let remaining = &calls[stopped_at..];
+        journal.replace(remaining)?;
+        report.remaining = remaining.len();
+        Ok(report)
     }
 }
 
-/// Format an HTTP error response into a descriptive string.
-async fn format_http_error(resp: reqwest::Response) -> String {
+/// Outcome of replaying the offline journal.
+#[derive(Debug, Default, Serialize)]
+pub struct FlushReport {
+    pub replayed: usize,
+    /// Calls the server rejected (4xx), with the error.
+    pub dropped: Vec<String>,
+    /// Calls still queued because the server is unreachable.
+    pub remaining: usize,
+}
+
+/// A fresh 128-bit `Idempotency-Key`.
+fn new_idempotency_key() -> String {
+    format!("{:016x}{:016x}", random_u64(), random_u64())
+}
+
+/// Whether an error means the server couldn't be reached at all, as opposed
+/// to an HTTP error response.
+fn is_unreachable(err: &(dyn std::error::Error + 'static)) -> bool {
+    matches!(ApiError::from_boxed(err), Some(ApiError::ConnectionFailed(_)))
+}
+
+/// One page of a list endpoint plus the cursor for the next one.
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+/// Split a list response into its items (under `key`) and `nextCursor`.
+fn split_page<T: DeserializeOwned>(
+    mut value: serde_json::Value,
+    key: &str,
+) -> Result<Page<T>, Box<dyn std::error::Error>> {
+    let items = match value.get_mut(key) {
+        Some(v) => serde_json::from_value(v.take())?,
+        None => return Err(format!("response has no `{key}` list").into()),
+    };
+    let next_cursor = value
+        .get("nextCursor")
+        .and_then(|c| c.as_str())
+        .filter(|c| !c.is_empty())
+        .map(str::to_string);
+    Ok(Page { items, next_cursor })
+}
+
+/// Parse a `Retry-After` header given in delta-seconds, capped at
+/// [`RATE_LIMIT_MAX_WAIT_SECS`]. HTTP-date values are ignored (the server only
+/// emits seconds) and fall back to exponential backoff.
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let secs = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(Duration::from_secs(secs.min(RATE_LIMIT_MAX_WAIT_SECS)))
+}
+
+/// Exponential backoff used when a 429 carries no usable `Retry-After`:
+/// 1s, 2s, 4s, ...
+pub(crate) fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs((1u64 << attempt.min(5)).min(RATE_LIMIT_MAX_WAIT_SECS))
+}
+
+/// Classify an HTTP error response, keeping its body and request ID.
+async fn api_error(resp: reqwest::Response) -> ApiError {
     let status = resp.status();
-    let code = status.as_u16();
     let reason = status.canonical_reason().unwrap_or("Unknown");
+    let retry = retry_after(resp.headers());
+    let request_id = resp
+        .headers()
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
     let body = resp.text().await.unwrap_or_default();
-    if body.is_empty() {
-        format!("HTTP {code} {reason}")
-    } else {
-        format!("HTTP {code} {reason}: {body}")
-    }
+    ApiError::from_status(HttpFailure::new(status.as_u16(), reason, body, request_id), retry)
 }
 
 /// Turn an HTTP response into a deserialized value or a descriptive error.
@@ -214,13 +502,76 @@ async fn handle_response<T: DeserializeOwned>(
 ) -> Result<T, Box<dyn std::error::Error>> {
     let status = resp.status();
     if status.is_success() {
-        let body = resp.text().await?;
+        let body = resp.text().await.map_err(ApiError::from_reqwest)?;
         if body.is_empty() {
             // Try to deserialize from `null` -- works for serde_json::Value.
             return Ok(serde_json::from_str("null")?);
         }
         Ok(serde_json::from_str(&body)?)
     } else {
-        Err(format_http_error(resp).await.into())
+        Err(api_error(resp).await.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+
+    use super::{backoff, retry_after, split_page, Page, RATE_LIMIT_MAX_WAIT_SECS};
+
+    #[test]
+    fn parses_retry_after_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+    }
+
+    #[test]
+    fn caps_long_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("3600"));
+        assert_eq!(
+            retry_after(&headers),
+            Some(Duration::from_secs(RATE_LIMIT_MAX_WAIT_SECS))
+        );
+    }
+
+    #[test]
+    fn ignores_http_date_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), None);
+        assert_eq!(retry_after(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn backoff_doubles_until_capped() {
+        assert_eq!(backoff(0), Duration::from_secs(1));
+        assert_eq!(backoff(1), Duration::from_secs(2));
+        assert_eq!(backoff(2), Duration::from_secs(4));
+        assert_eq!(backoff(10), Duration::from_secs(RATE_LIMIT_MAX_WAIT_SECS));
+    }
+
+    #[test]
+    fn split_page_reads_items_and_cursor() {
+        let page: Page<u32> =
+            split_page(serde_json::json!({ "items": [1, 2], "nextCursor": "c2" }), "items").unwrap();
+        assert_eq!(page.items, vec![1, 2]);
+        assert_eq!(page.next_cursor.as_deref(), Some("c2"));
+    }
+
+    #[test]
+    fn split_page_treats_missing_or_empty_cursor_as_last_page() {
+        let page: Page<u32> = split_page(serde_json::json!({ "items": [] }), "items").unwrap();
+        assert!(page.next_cursor.is_none());
+        let page: Page<u32> =
+            split_page(serde_json::json!({ "items": [], "nextCursor": "" }), "items").unwrap();
+        assert!(page.next_cursor.is_none());
+        assert!(split_page::<u32>(serde_json::json!({}), "items").is_err());
     }
 }
@@ -4,6 +4,7 @@ use serde_json::json;
 use tabled::{Table, Tabled};
 
 use crate::client::Client;
+use crate::commands::completions;
 
 #[derive(Args)]
 pub struct AgentArgs {
@@ -27,6 +28,7 @@ enum AgentCommand {
     /// Stop (suspend) an agent session
     Stop {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         id: String,
     },
 }
@@ -84,7 +86,7 @@ pub async fn run(args: AgentArgs, client: &Client, human: bool) -> Result<(), Bo
             if let Some(branch) = worktree {
                 body["worktreeBranch"] = json!(branch);
             }
-            let result: serde_json::Value = client.post_json("/api/sessions", &body).await?;
+            let result: serde_json::Value = client.post_json_idempotent("/api/sessions", &body, None).await?;
             println!("{}", serde_json::to_string_pretty(&result)?);
         }
         AgentCommand::List => {
@@ -4,6 +4,7 @@ use clap::{Args, Subcommand};
 use serde_json::json;
 
 use crate::client::Client;
+use crate::commands::completions;
 
 #[derive(Args)]
 pub struct BrowserArgs {
@@ -16,6 +17,7 @@ enum BrowserCommand {
     /// Navigate browser session to a URL
     Navigate {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         session_id: String,
         /// URL to navigate to
         url: String,
@@ -23,6 +25,7 @@ enum BrowserCommand {
     /// Take a screenshot of the browser session
     Screenshot {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         session_id: String,
         /// Output file path (defaults to screenshot.png)
         #[arg(long, default_value = "screenshot.png")]
@@ -31,11 +34,13 @@ enum BrowserCommand {
     /// Get accessibility snapshot of the browser session
     Snapshot {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         session_id: String,
     },
     /// Click at coordinates in the browser session
     Click {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         session_id: String,
         /// X coordinate
         x: f64,
@@ -45,6 +50,7 @@ enum BrowserCommand {
     /// Type text in the browser session
     Type {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         session_id: String,
         /// Text to type
         text: String,
@@ -55,6 +61,7 @@ enum BrowserCommand {
     /// Evaluate JavaScript in the browser session
     Evaluate {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         session_id: String,
         /// JavaScript expression to evaluate
         expr: String,
@@ -62,11 +69,13 @@ enum BrowserCommand {
     /// Navigate back in the browser session
     Back {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         session_id: String,
     },
     /// Navigate forward in the browser session
     Forward {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         session_id: String,
     },
 }
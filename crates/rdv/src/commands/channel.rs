@@ -4,6 +4,7 @@ use serde_json::json;
 use tabled::{Table, Tabled};
 
 use crate::client::Client;
+use crate::time::format_timestamp;
 
 #[derive(Args)]
 pub struct ChannelArgs {
@@ -214,7 +215,7 @@ pub async fn run(
                         };
                         println!(
                             "[{}] {}{}: {}",
-                            msg.created_at, msg.from_session_name, thread, msg.body
+                            format_timestamp(&msg.created_at), msg.from_session_name, thread, msg.body
                         );
                     }
                 }
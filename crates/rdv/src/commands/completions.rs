@@ -0,0 +1,112 @@
+//! Shell completions and man pages.
+//!
+//! Completions are dynamic: the shell calls back into `rdv` (with
+//! `COMPLETE=<shell>` set) on every Tab, so session and folder IDs can be
+//! offered from the running server. When it can't be reached those
+//! arguments simply get no suggestions.
+
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Args;
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
+use clap_complete::env::Shells;
+use serde::Deserialize;
+
+use crate::client::Client;
+use crate::config::ServerConfig;
+
+/// How long a Tab press may wait on the server.
+const LOOKUP_TIMEOUT: Duration = Duration::from_millis(1500);
+
+#[derive(Args)]
+pub struct CompletionsArgs {
+    /// Shell to print the registration script for
+    #[arg(value_parser = ["bash", "zsh", "fish"])]
+    shell: String,
+}
+
+#[derive(Args)]
+pub struct ManArgs {
+    /// Write one page per subcommand into this directory instead of
+    /// printing the top-level page
+    #[arg(long)]
+    dir: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct Named {
+    id: String,
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SessionsResponse {
+    sessions: Vec<Named>,
+}
+
+#[derive(Deserialize)]
+struct ProjectsResponse {
+    projects: Vec<Named>,
+}
+
+/// Fetch candidates from the server. Completion runs before the CLI's own
+/// runtime does anything useful, so this uses a throwaway runtime on its
+/// own thread.
+fn lookup<T, F>(path: &'static str, items: F) -> Vec<CompletionCandidate>
+where
+    T: for<'de> Deserialize<'de> + Send + 'static,
+    F: FnOnce(T) -> Vec<Named> + Send + 'static,
+{
+    let fetched = std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().ok()?;
+        runtime.block_on(async {
+            let client = Client::new(&ServerConfig::from_env());
+            tokio::time::timeout(LOOKUP_TIMEOUT, client.get::<T>(path)).await.ok()?.ok()
+        })
+    })
+    .join()
+    .ok()
+    .flatten();
+    fetched
+        .map(items)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|n| CompletionCandidate::new(n.id).help(n.name.map(Into::into)))
+        .collect()
+}
+
+/// Completion source for arguments taking a session ID.
+pub(crate) fn session_ids() -> ArgValueCandidates {
+    ArgValueCandidates::new(|| lookup("/api/sessions", |r: SessionsResponse| r.sessions))
+}
+
+/// Completion source for arguments taking a folder (project) ID.
+pub(crate) fn folder_ids() -> ArgValueCandidates {
+    ArgValueCandidates::new(|| lookup("/api/projects", |r: ProjectsResponse| r.projects))
+}
+
+pub fn run_completions(args: CompletionsArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let shells = Shells::builtins();
+    let shell = shells
+        .completer(&args.shell)
+        .ok_or_else(|| format!("unsupported shell {}", args.shell))?;
+    let exe = std::env::current_exe()?;
+    let completer = exe.to_str().ok_or("rdv's path is not valid UTF-8")?;
+    let bin = exe.file_name().and_then(OsStr::to_str).unwrap_or("rdv");
+    shell.write_registration("COMPLETE", "rdv", bin, completer, &mut std::io::stdout())?;
+    Ok(())
+}
+
+pub fn run_man(args: ManArgs, cmd: clap::Command) -> Result<(), Box<dyn std::error::Error>> {
+    match args.dir {
+        Some(dir) => {
+            std::fs::create_dir_all(&dir)?;
+            clap_mangen::generate_to(cmd, &dir)?;
+            eprintln!("Wrote man pages to {}", dir.display());
+        }
+        None => clap_mangen::Man::new(cmd).render(&mut std::io::stdout())?,
+    }
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use serde::Serialize;
+use serde_json::json;
+use tabled::{Table, Tabled};
+
+use crate::client::Client;
+use crate::config::{env_override, parse_port, parse_settings, setting, settings_path, Kind, Setting, SETTINGS};
+use crate::time::DisplayZone;
+
+#[derive(Args)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    command: ConfigCommand,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print the settings file's path (RDV_CONFIG overrides it)
+    Path,
+    /// Show effective settings and where each comes from
+    Get {
+        /// One key; all keys when omitted
+        key: Option<String>,
+    },
+    /// Write a key to the settings file, keeping its comments and layout
+    Set { key: String, value: String },
+    /// Remove a key from the settings file
+    Unset { key: String },
+    /// Check the settings file for type errors and unknown keys
+    Validate {
+        /// File to check instead of the configured one
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+    /// Show the server's effective configuration, secrets redacted
+    Server,
+}
+
+#[derive(Debug, Serialize, Tabled)]
+struct Effective {
+    #[tabled(rename = "Key")]
+    key: &'static str,
+    #[tabled(rename = "Value")]
+    value: String,
+    /// `env`, `file` or `default`.
+    #[tabled(rename = "Source")]
+    source: String,
+}
+
+/// Value and source for one setting: environment, then file, then default.
+/// An env var rdv would ignore (say `RDV_API_PORT=abc`) isn't the source.
+fn effective(s: &'static Setting, file: &toml::Table) -> Effective {
+    let (value, source) = match (env_override(s), file.get(s.key)) {
+        (Some(v), _) => (v, format!("env {}", s.env)),
+        (None, Some(toml::Value::String(v))) => (v.clone(), "file".to_string()),
+        (None, Some(v)) => (v.to_string(), "file".to_string()),
+        (None, None) => (s.default.to_string(), "default".to_string()),
+    };
+    Effective { key: s.key, value, source }
+}
+
+/// The settings file as `rdv` reads it: empty when missing or broken. Unknown
+/// keys and parse errors go to stderr.
+fn load_file(path: &std::path::Path) -> toml::Table {
+    let text = std::fs::read_to_string(path).unwrap_or_default();
+    match parse_settings(&text) {
+        Ok((_, unknown)) => {
+            for key in unknown {
+                eprintln!("rdv: {}: unknown key `{key}` (see `rdv config validate`)", path.display());
+            }
+            toml::from_str(&text).unwrap_or_default()
+        }
+        Err(e) => {
+            eprintln!("rdv: ignoring {}: {e}", path.display());
+            toml::Table::new()
+        }
+    }
+}
+
+/// Convert `value` to the TOML type `kind` expects.
+fn typed_value(kind: Kind, key: &str, value: &str) -> Result<toml_edit::Value, String> {
+    match kind {
+        Kind::Path => Ok(value.into()),
+        Kind::Port => parse_port(value)
+            .map(|p| i64::from(p).into())
+            .ok_or_else(|| format!("{key} must be a port number (1-65535)")),
+        Kind::Bool => match value {
+            "true" | "1" | "yes" => Ok(true.into()),
+            "false" | "0" | "no" => Ok(false.into()),
+            _ => Err(format!("{key} must be true or false")),
+        },
+        Kind::Zone => match DisplayZone::parse(value) {
+            Some(_) => Ok(value.into()),
+            None => Err(format!("{key} must be local, UTC or an IANA zone name")),
+        },
+    }
+}
+
+fn known_keys() -> String {
+    SETTINGS.iter().map(|s| s.key).collect::<Vec<_>>().join(", ")
+}
+
+fn read_document(path: &std::path::Path) -> Result<toml_edit::DocumentMut, Box<dyn std::error::Error>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(t) => t,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("cannot read {}: {e}", path.display()).into()),
+    };
+    text.parse()
+        .map_err(|e| format!("{} is not valid TOML: {e}", path.display()).into())
+}
+
+fn write_document(path: &std::path::Path, doc: &toml_edit::DocumentMut) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, doc.to_string()).map_err(|e| format!("cannot write {}: {e}", path.display()).into())
+}
+
+pub async fn run(args: ConfigArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let path = settings_path();
+    match args.command {
+        ConfigCommand::Path => println!("{}", path.display()),
+        ConfigCommand::Get { key } => {
+            let file = load_file(&path);
+            let rows: Vec<Effective> = match key {
+                Some(k) => {
+                    let s = setting(&k).ok_or_else(|| format!("unknown key {k:?}; known keys: {}", known_keys()))?;
+                    vec![effective(s, &file)]
+                }
+                None => SETTINGS.iter().map(|s| effective(s, &file)).collect(),
+            };
+            if human {
+                println!("{}", Table::new(rows));
+            } else {
+                println!("{}", serde_json::to_string_pretty(&rows)?);
+            }
+        }
+        ConfigCommand::Set { key, value } => {
+            let s = setting(&key).ok_or_else(|| format!("unknown key {key:?}; known keys: {}", known_keys()))?;
+            let typed = typed_value(s.kind, &key, &value)?;
+            let mut doc = read_document(&path)?;
+            doc[s.key] = toml_edit::value(typed);
+            write_document(&path, &doc)?;
+            if human {
+                println!("Set {key} = {value} in {}", path.display());
+                if env_override(s).is_some() {
+                    println!("Note: {} is set and overrides the file", s.env);
+                }
+            } else {
+                println!("{}", json!({ "key": key, "value": value, "path": path }));
+            }
+        }
+        ConfigCommand::Unset { key } => {
+            let s = setting(&key).ok_or_else(|| format!("unknown key {key:?}; known keys: {}", known_keys()))?;
+            let mut doc = read_document(&path)?;
+            let removed = doc.remove(s.key).is_some();
+            if removed {
+                write_document(&path, &doc)?;
+            }
+            if human {
+                let verb = if removed { "Removed" } else { "Not set:" };
+                println!("{verb} {key} in {}", path.display());
+            } else {
+                println!("{}", json!({ "key": key, "removed": removed, "path": path }));
+            }
+        }
+        ConfigCommand::Validate { file } => {
+            let path = file.unwrap_or(path);
+            let text = match std::fs::read_to_string(&path) {
+                Ok(t) => t,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    if human {
+                        println!("{} does not exist; defaults apply", path.display());
+                    } else {
+                        println!("{}", json!({ "path": path, "exists": false, "valid": true, "unknownKeys": [] }));
+                    }
+                    return Ok(());
+                }
+                Err(e) => return Err(format!("cannot read {}: {e}", path.display()).into()),
+            };
+            let (unknown, error) = match parse_settings(&text) {
+                Ok((_, unknown)) => (unknown, None),
+                Err(e) => (Vec::new(), Some(e)),
+            };
+            if human {
+                for key in &unknown {
+                    println!("warning: unknown key `{key}`; known keys: {}", known_keys());
+                }
+                match error {
+                    Some(ref e) => println!("{}: {e}", path.display()),
+                    None => println!("{} is valid", path.display()),
+                }
+            } else {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({
+                        "path": path,
+                        "exists": true,
+                        "valid": error.is_none(),
+                        "unknownKeys": unknown,
+                        "error": error,
+                    }))?
+                );
+            }
+            if error.is_some() {
+                return Err(format!("{} is invalid", path.display()).into());
+            }
+        }
+        ConfigCommand::Server => {
+            let config: serde_json::Value = client.get("/api/config").await?;
+            if let (true, Some(map)) = (human, config.as_object()) {
+                for (k, v) in map {
+                    let shown = v.as_str().map(String::from).unwrap_or_else(|| v.to_string());
+                    println!("{k} = {shown}");
+                }
+            } else {
+                println!("{}", serde_json::to_string_pretty(&config)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::typed_value;
+    use crate::config::Kind;
+
+    #[test]
+    fn set_values_are_typed_by_key() {
+        assert_eq!(typed_value(Kind::Port, "api_port", "7001").unwrap().as_integer(), Some(7001));
+        assert_eq!(typed_value(Kind::Bool, "trace", "yes").unwrap().as_bool(), Some(true));
+        assert!(typed_value(Kind::Port, "api_port", "99999").is_err());
+        assert!(typed_value(Kind::Port, "api_port", "0").is_err());
+        assert!(typed_value(Kind::Zone, "timezone", "Nowhere").is_err());
+    }
+}
@@ -11,6 +11,7 @@ use clap::{Args, Subcommand};
 use serde_json::json;
 
 use crate::client::Client;
+use crate::provider::Provider;
 
 #[derive(Args)]
 pub struct CrownArgs {
@@ -28,8 +29,8 @@ enum CrownCommand {
         /// Number of candidate agents to fan out
         #[arg(long, default_value = "2")]
         count: u32,
-        /// Agent provider (claude, codex, gemini, opencode)
-        #[arg(long, default_value = "claude")]
+        /// Agent provider
+        #[arg(long, default_value = "claude", value_parser = Provider::NAMES)]
         provider: String,
         /// The shared task prompt
         #[arg(long)]
@@ -0,0 +1,202 @@
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::backup;
+use crate::client::Client;
+use crate::commands::pause;
+use crate::config::{data_dir, settings_file_report, settings_path};
+use crate::preflight::{self, Check};
+use crate::queue::Journal;
+
+#[derive(Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct DoctorArgs {
+    #[command(subcommand)]
+    command: Option<DoctorCommand>,
+    /// Replay calls queued while the server was unreachable
+    #[arg(long)]
+    flush_queue: bool,
+}
+
+#[derive(Subcommand)]
+enum DoctorCommand {
+    /// Snapshot the databases, profiles and recordings into one archive,
+    /// e.g. before a risky upgrade
+    Backup {
+        /// Archive to write (.tar.gz)
+        path: PathBuf,
+    },
+    /// Restore a snapshot taken with `rdv doctor backup`; stop the server first
+    Restore {
+        /// Archive to restore
+        path: PathBuf,
+        /// Restore even if the server is running or the snapshot's schema is newer
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct ProbeResult {
+    ok: bool,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReadyResponse {
+    #[serde(default)]
+    checks: std::collections::BTreeMap<String, ProbeResult>,
+}
+
+async fn run_checks(client: &Client) -> Vec<Check> {
+    let mut checks = preflight::for_host(&data_dir()).await;
+
+    match settings_file_report() {
+        Ok(unknown) if unknown.is_empty() => {}
+        Ok(unknown) => checks.push(Check::warn(
+            "config",
+            format!("{}: unknown key(s) {}", settings_path().display(), unknown.join(", ")),
+        )),
+        Err(e) => checks.push(Check::warn("config", format!("ignoring {}: {e}", settings_path().display()))),
+    }
+
+    match Journal::open_default().load() {
+        Ok(queued) if queued.is_empty() => {}
+        Ok(queued) => checks.push(Check::fail(
+            "queue",
+            format!("{} call(s) queued while offline; run `rdv doctor --flush-queue`", queued.len()),
+        )),
+        Err(e) => checks.push(Check::fail("queue", format!("cannot read offline queue: {e}"))),
+    }
+
+    match client.get_raw("/api/healthz").await {
+        Ok((status, _)) if status.is_success() => checks.push(Check::pass("api")),
+        Ok((status, _)) => checks.push(Check::fail("api", format!("healthz returned HTTP {}", status.as_u16()))),
+        Err(e) => {
+            // Nothing else can be checked without the API server.
+            checks.push(Check::fail("api", format!("unreachable: {e}")));
+            return checks;
+        }
+    }
+
+    match client.get_raw("/api/readyz").await {
+        Ok((_, body)) => match serde_json::from_str::<ReadyResponse>(&body) {
+            Ok(ready) => {
+                for (name, probe) in ready.checks {
+                    checks.push(if probe.ok {
+                        Check::pass(&name)
+                    } else {
+                        Check::fail(&name, probe.error.unwrap_or_else(|| "not ready".into()))
+                    });
+                }
+            }
+            Err(e) => checks.push(Check::fail("readyz", format!("unexpected response: {e}"))),
+        },
+        Err(e) => checks.push(Check::fail("readyz", e.to_string())),
+    }
+
+    match client.get_raw("/api/sessions?limit=1").await {
+        Ok((status, _)) if status.is_success() => checks.push(Check::pass("auth")),
+        Ok((status, _)) if status.as_u16() == 401 => checks.push(Check::fail(
+            "auth",
+            "API key rejected; check RDV_API_KEY or ~/.remote-dev/rdv/.local-key",
+        )),
+        Ok((status, _)) => checks.push(Check::fail("auth", format!("HTTP {}", status.as_u16()))),
+        Err(e) => checks.push(Check::fail("auth", e.to_string())),
+    }
+
+    // A pause isn't a failure, but it explains why nothing automatic happens.
+    match pause::fetch_state(client).await {
+        Ok(state) => {
+            if let Some(p) = state.global {
+                checks.push(Check::pass_with("automation", format!("paused {}", p.describe())));
+            }
+        }
+        Err(e) => checks.push(Check::fail("automation", format!("pause state unavailable: {e}"))),
+    }
+
+    checks
+}
+
+pub async fn run(args: DoctorArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
+    match args.command {
+        Some(DoctorCommand::Backup { path }) => {
+            let manifest = backup::create(&data_dir(), &path).await?;
+            if human {
+                println!("Wrote {} ({})", path.display(), manifest.contents.join(", "));
+            } else {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({ "path": path, "manifest": manifest }))?
+                );
+            }
+            return Ok(());
+        }
+        Some(DoctorCommand::Restore { path, force }) => {
+            let running = matches!(client.get_raw("/api/healthz").await, Ok((status, _)) if status.is_success());
+            if running && !force {
+                return Err("the Remote Dev server is running; stop it before restoring (or pass --force)".into());
+            }
+            let (manifest, kept) = backup::restore(&path, &data_dir(), force).await?;
+            if human {
+                println!("Restored {} from {}", manifest.contents.join(", "), manifest.created_at);
+                for k in &kept {
+                    println!("  previous copy kept at {}", k.display());
+                }
+            } else {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({ "manifest": manifest, "kept": kept }))?
+                );
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
+    if args.flush_queue {
+        let report = client.flush_queue().await?;
+        if human {
+            println!("Replayed {} queued call(s); {} still queued.", report.replayed, report.remaining);
+            for d in &report.dropped {
+                println!("  {} {}", "dropped".red(), d);
+            }
+        } else {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        if report.remaining > 0 {
+            return Err("server still unreachable; queued calls kept".into());
+        }
+        return Ok(());
+    }
+
+    let checks = run_checks(client).await;
+    let failed = checks.iter().filter(|c| !c.ok).count();
+    if human {
+        for c in &checks {
+            let mark = match (c.ok, c.warning) {
+                (false, _) => "\u{2717}".red(),
+                (true, true) => "!".yellow(),
+                (true, false) => "\u{2713}".green(),
+            };
+            match c.detail {
+                Some(ref d) => println!("{mark} {}: {}", c.name.bold(), d),
+                None => println!("{mark} {}", c.name.bold()),
+            }
+        }
+    } else {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({ "ok": failed == 0, "checks": checks }))?
+        );
+    }
+    if failed > 0 {
+        return Err(format!("{failed} check(s) failed").into());
+    }
+    Ok(())
+}
+
@@ -0,0 +1,88 @@
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use crate::client::Client;
+use crate::time::format_timestamp;
+
+#[derive(Args)]
+pub struct GithubArgs {
+    #[command(subcommand)]
+    command: GithubCommand,
+}
+
+#[derive(Subcommand)]
+enum GithubCommand {
+    /// Open PRs and issues and CI status for a repository, from the
+    /// server's cache
+    Stats {
+        /// Repository ID, as listed on the GitHub stats page
+        repo_id: String,
+        /// Fetch from GitHub now instead of waiting for the cache to expire
+        #[arg(long)]
+        refresh: bool,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CiStatus {
+    /// passing, failing, pending, or unknown.
+    state: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RepositoryStats {
+    #[serde(rename = "openPRCount")]
+    open_pr_count: u32,
+    open_issue_count: u32,
+    ci_status: Option<CiStatus>,
+    /// When the server last fetched from GitHub; `None` before the first fetch.
+    last_fetched_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Repository {
+    id: String,
+    full_name: String,
+    default_branch: String,
+    stats: RepositoryStats,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryResponse {
+    /// Absent when a refresh names a repository the user no longer has.
+    repository: Option<Repository>,
+}
+
+pub async fn run(args: GithubArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
+    match args.command {
+        GithubCommand::Stats { repo_id, refresh } => {
+            let path = format!("/api/github/stats/{repo_id}");
+            let resp: RepositoryResponse = if refresh {
+                serde_json::from_value(client.post_empty(&path).await?)?
+            } else {
+                client.get(&path).await?
+            };
+            let repo = resp.repository.ok_or_else(|| format!("repository {repo_id} not found"))?;
+            if human {
+                let stats = &repo.stats;
+                println!("{}", repo.full_name);
+                println!("  Open PRs:    {}", stats.open_pr_count);
+                println!("  Open issues: {}", stats.open_issue_count);
+                println!(
+                    "  CI:          {} ({})",
+                    stats.ci_status.as_ref().map_or("none", |c| c.state.as_str()),
+                    repo.default_branch
+                );
+                match stats.last_fetched_at {
+                    Some(ref t) => println!("  Fetched:     {}", format_timestamp(t)),
+                    None => println!("  Fetched:     never"),
+                }
+            } else {
+                println!("{}", serde_json::to_string_pretty(&repo)?);
+            }
+        }
+    }
+    Ok(())
+}
@@ -4,6 +4,7 @@ use clap::{Args, Subcommand};
 use serde_json::json;
 
 use crate::client::Client;
+use crate::process::{self, RunOptions};
 
 #[derive(Args)]
 pub struct HookArgs {
@@ -84,7 +85,7 @@ fn inspect_bash_payload(payload: &serde_json::Value) -> Option<BashInspection> {
     // If no explicit branch (bare `git push` or `git push origin`), assume it may target main
     let targets_main = is_git_push
         && extract_branch_from_push(&command)
-            .map_or(true, |b| b == "main" || b == "master");
+            .is_none_or(|b| b == "main" || b == "master");
     Some(BashInspection {
         command,
         targets_main,
@@ -105,7 +106,7 @@ fn strip_mention_tokens(body: &str) -> String {
     while let Some(start) = rest.find(PREFIX) {
         result.push_str(&rest[..start]);
         let after_prefix = &rest[start + PREFIX.len()..];
-        if after_prefix.len() >= UUID_LEN + 1 && after_prefix.as_bytes()[UUID_LEN] == SUFFIX as u8 {
+        if after_prefix.len() > UUID_LEN && after_prefix.as_bytes()[UUID_LEN] == SUFFIX as u8 {
             // Replace with @<first-8-chars-of-uuid>
             result.push('@');
             result.push_str(&after_prefix[..8]);
@@ -294,7 +295,7 @@ async fn print_peer_digest(client: &Client) {
                     );
                     let raw_body = msg.get("body").and_then(|v| v.as_str()).unwrap_or("");
                     let body = sanitize_for_digest(&strip_mention_tokens(raw_body));
-                    let is_broadcast = msg.get("toSessionId").map_or(true, |v| v.is_null());
+                    let is_broadcast = msg.get("toSessionId").is_none_or(|v| v.is_null());
                     let target = if is_broadcast { " (broadcast)" } else { "" };
                     eprintln!("\u{1f4e8} {from}{target}: {body}");
                 }
@@ -438,117 +439,6 @@ async fn report_proxy_state(client: &Client) {
     }
 }
 
-// ── Git identity guard ──────────────────────────────────────────────
-
-/// Check if a git command in a sensitive folder would leak identity.
-/// Accepts a pre-parsed PreToolUse payload, inspects for git commit/push commands,
-/// and calls the git-guard API to evaluate identity risk.
-/// Returns true if the tool use should be blocked.
-async fn check_git_identity_guard(client: &Client, payload: &serde_json::Value) -> bool {
-    // Only check Bash tool calls
-    let tool_name = payload
-        .get("tool_name")
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-    if tool_name != "Bash" {
-        return false;
-    }
-
-    let command = payload
-        .get("tool_input")
-        .and_then(|v| v.get("command"))
-        .and_then(|v| v.as_str())
-        .unwrap_or("");
-
-    // Check if the command is a git commit or git push
-    let is_git_commit = command.contains("git commit") || command.contains("git-commit");
-    let is_git_push = command.contains("git push") || command.contains("git-push");
-    if !is_git_commit && !is_git_push {
-        return false;
-    }
-
-    let operation = if is_git_push { "push" } else { "commit" };
-
-    let Some(sid) = client.session_id() else {
-        return false;
-    };
-
-    // Get the session's project and folder IDs
-    #[derive(serde::Deserialize)]
-    struct SessionInfo {
-        #[serde(rename = "projectId")]
-        project_id: Option<String>,
-        #[serde(rename = "folderId")]
-        folder_id: Option<String>,
-    }
-
-    let session: SessionInfo = match client.get(&format!("/api/sessions/{sid}")).await {
-        Ok(s) => s,
-        Err(_) => return false,
-    };
-
-    // Prefer RDV_PROJECT_ID env var, then session.projectId, then fall back to folderId
-    let project_id = std::env::var("RDV_PROJECT_ID")
-        .ok()
-        .or_else(|| session.project_id.clone())
-        .or_else(|| session.folder_id.clone());
-
-    let Some(ref folder_id) = project_id else {
-        return false;
-    };
-
-    // Read git identity from environment (set by session-service)
-    let proposed_name = std::env::var("GIT_AUTHOR_NAME")
-        .or_else(|_| std::env::var("GIT_COMMITTER_NAME"))
-        .unwrap_or_default();
-    let proposed_email = std::env::var("GIT_AUTHOR_EMAIL")
-        .or_else(|_| std::env::var("GIT_COMMITTER_EMAIL"))
-        .unwrap_or_default();
-
-    // Always call the guard API — the server determines if the folder is sensitive
-    // even when no identity env vars are set (which is the most dangerous case)
-    let guard_payload = json!({
-        "projectId": folder_id,
-        "folderId": folder_id,
-        "proposedName": proposed_name,
-        "proposedEmail": proposed_email,
-        "operation": operation,
-    });
-
-    #[derive(serde::Deserialize)]
-    struct GuardResult {
-        risk: String,
-        reason: Option<String>,
-    }
-
-    let result: GuardResult = match client
-        .post_json(&format!("/api/folders/{folder_id}/git-guard"), &guard_payload)
-        .await
-    {
-        Ok(val) => match serde_json::from_value(val) {
-            Ok(r) => r,
-            Err(_) => return false,
-        },
-        Err(_) => return false,
-    };
-
-    match result.risk.as_str() {
-        "block" => {
-            if let Some(reason) = &result.reason {
-                eprintln!("\u{1f6e1}\u{fe0f}  Git identity guard: {reason}");
-            }
-            true
-        }
-        "warn" => {
-            if let Some(reason) = &result.reason {
-                eprintln!("\u{26a0}\u{fe0f}  Git identity warning: {reason}");
-            }
-            false
-        }
-        _ => false,
-    }
-}
-
 // ── Beads check ────────────────────────────────────────────────────
 
 /// Check if there are in-progress beads issues.
@@ -560,21 +450,12 @@ async fn check_beads_unfinished() -> Option<String> {
     }
 
     // Run bd list to check for in-progress issues
-    let output = match tokio::process::Command::new("bd")
-        .args(["list", "--status=in_progress", "--json", "--quiet"])
-        .output()
+    // Missing, failing or hung bd never blocks stop; a hook has to return.
+    let opts = RunOptions::LOCAL.timeout(std::time::Duration::from_secs(10));
+    let stdout = process::run("bd", &["list", "--status=in_progress", "--json", "--quiet"], None, opts)
         .await
-    {
-        Ok(o) => o,
-        Err(_) => return None, // bd not available, skip check
-    };
-
-    if !output.status.success() {
-        return None; // bd command failed, don't block stop
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stdout = stdout.trim();
+        .ok()?;
+    let stdout = stdout.as_str();
     if stdout.is_empty() || stdout == "[]" || stdout == "null" {
         return None;
     }
@@ -727,9 +608,6 @@ pub async fn run(
                 report_proxy_state(client).await;
             }
 
-            if check_git_identity_guard(client, &payload).await {
-                std::process::exit(2);
-            }
         }
         HookCommand::PostToolUse => {
             // Read stdin, parse as JSON to check for Bash git push
@@ -2,10 +2,12 @@ use clap::Args;
 use serde_json::json;
 
 use crate::client::Client;
+use crate::commands::completions;
 
 #[derive(Args)]
 pub struct SetStatusArgs {
     /// Session ID
+    #[arg(add = completions::session_ids())]
     session_id: String,
     /// Status key name
     key: String,
@@ -23,6 +25,7 @@ pub struct SetStatusArgs {
 #[derive(Args)]
 pub struct ClearStatusArgs {
     /// Session ID
+    #[arg(add = completions::session_ids())]
     session_id: String,
     /// Status key to clear
     key: String,
@@ -31,6 +34,7 @@ pub struct ClearStatusArgs {
 #[derive(Args)]
 pub struct SetProgressArgs {
     /// Session ID
+    #[arg(add = completions::session_ids())]
     session_id: String,
     /// Progress value (0.0 to 1.0)
     value: f64,
@@ -42,12 +46,14 @@ pub struct SetProgressArgs {
 #[derive(Args)]
 pub struct ClearProgressArgs {
     /// Session ID
+    #[arg(add = completions::session_ids())]
     session_id: String,
 }
 
 #[derive(Args)]
 pub struct LogArgs {
     /// Session ID
+    #[arg(add = completions::session_ids())]
     session_id: String,
     /// Log message
     #[arg(trailing_var_arg = true, required = true)]
@@ -21,6 +21,7 @@ use serde_json::json;
 use tabled::{Table, Tabled};
 
 use crate::client::Client;
+use crate::error::ApiError;
 
 const POLL_INTERVAL_SECS: u64 = 2;
 
@@ -327,7 +328,7 @@ pub async fn run(
                     }
                 }
                 // The endpoint ships with stage 2 — degrade gracefully on 404.
-                Err(e) if e.to_string().starts_with("HTTP 404") => {
+                Err(e) if matches!(ApiError::from_boxed(e.as_ref()), Some(ApiError::NotFound(_))) => {
                     if human {
                         println!(
                             "Size preview unavailable (endpoint not present on this instance)."
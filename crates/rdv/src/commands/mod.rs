@@ -1,14 +1,20 @@
 pub mod agent;
 pub mod browser;
 pub mod channel;
+pub mod completions;
+pub mod config;
 pub mod context;
 pub mod crown; // [oyej] best-of-N run-and-compare
 pub mod delegate; // [oyej] cross-instance delegation
+pub mod doctor;
+pub mod github;
 pub mod group;
 pub mod hook;
 pub mod indicator;
 pub mod migrate; // server-to-server project migration (stage 3)
+pub mod note;
 pub mod notification;
+pub mod pause;
 pub mod peer;
 pub mod project;
 pub mod screen;
@@ -16,6 +22,9 @@ pub mod send;
 pub mod session;
 pub mod status;
 pub mod system;
+pub mod task;
 pub mod teams;
 pub mod tmux_compat;
+pub mod tmux_hook;
+pub mod usage;
 pub mod worktree;
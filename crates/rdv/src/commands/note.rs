@@ -0,0 +1,263 @@
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tabled::{Table, Tabled};
+
+use crate::client::Client;
+use crate::commands::completions;
+use crate::time::format_timestamp;
+
+#[derive(Args)]
+pub struct NoteArgs {
+    #[command(subcommand)]
+    command: NoteCommand,
+}
+
+#[derive(Subcommand)]
+enum NoteCommand {
+    /// Capture a note, filed under the session's folder
+    Add {
+        /// Note text
+        content: String,
+        /// Title (default: the first line of the text)
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long, default_value = "note", value_parser = ["note", "artifact", "summary"])]
+        r#type: String,
+        /// Tag to attach (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Session ID (defaults to RDV_SESSION_ID)
+        #[arg(long, add = completions::session_ids())]
+        session_id: Option<String>,
+    },
+    /// List notes, newest first
+    List {
+        /// Folder ID (defaults to RDV_PROJECT_ID)
+        #[arg(long, add = completions::folder_ids())]
+        folder_id: Option<String>,
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Show one note
+    Show { id: String },
+    /// Change a note's title, text or tags
+    Edit {
+        id: String,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        content: Option<String>,
+        /// Replace the tags (repeatable)
+        #[arg(long = "tag")]
+        tags: Option<Vec<String>>,
+    },
+    /// Delete a note
+    Delete { id: String },
+    /// Search note titles, text and tags (case-insensitive)
+    Search {
+        query: String,
+        /// Folder ID (defaults to RDV_PROJECT_ID)
+        #[arg(long, add = completions::folder_ids())]
+        folder_id: Option<String>,
+        #[arg(long, default_value = "20")]
+        limit: u32,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Note {
+    id: String,
+    folder_id: Option<String>,
+    /// `note`, `artifact`, or `summary`.
+    #[serde(rename = "type")]
+    note_type: Option<String>,
+    title: Option<String>,
+    content: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotesResponse {
+    notes: Vec<Note>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NoteResponse {
+    note: Note,
+}
+
+#[derive(Tabled)]
+struct NoteRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Type")]
+    note_type: String,
+    #[tabled(rename = "Tags")]
+    tags: String,
+    #[tabled(rename = "Note")]
+    summary: String,
+}
+
+/// First line of the title, else of the content, cut for a table cell.
+fn headline(note: &Note) -> String {
+    let text = note.title.as_deref().unwrap_or(&note.content);
+    let line = text.lines().next().unwrap_or("");
+    if line.chars().count() > 60 {
+        format!("{}...", line.chars().take(57).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+impl From<&Note> for NoteRow {
+    fn from(n: &Note) -> Self {
+        Self {
+            id: n.id.clone(),
+            note_type: n.note_type.clone().unwrap_or_default(),
+            tags: n.tags.join(","),
+            summary: headline(n),
+        }
+    }
+}
+
+fn print_note(note: &Note) {
+    if let Some(ref t) = note.title {
+        println!("{t}");
+    }
+    let mut meta = vec![note.id.clone()];
+    meta.extend(note.note_type.clone());
+    if !note.tags.is_empty() {
+        meta.push(format!("tags: {}", note.tags.join(",")));
+    }
+    meta.extend(note.created_at.as_deref().map(format_timestamp));
+    println!("[{}]", meta.join(" | "));
+    println!();
+    println!("{}", note.content.trim_end());
+}
+
+fn print_notes(notes: &[Note], human: bool, empty: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if !human {
+        println!("{}", serde_json::to_string_pretty(notes)?);
+    } else if notes.is_empty() {
+        println!("{empty}");
+    } else {
+        let rows: Vec<NoteRow> = notes.iter().map(NoteRow::from).collect();
+        println!("{}", Table::new(rows));
+    }
+    Ok(())
+}
+
+fn session_or_env(client: &Client, session_id: Option<String>) -> Result<String, Box<dyn std::error::Error>> {
+    session_id
+        .or_else(|| client.session_id().map(String::from))
+        .ok_or_else(|| "no --session-id given and RDV_SESSION_ID is not set".into())
+}
+
+pub async fn run(args: NoteArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
+    match args.command {
+        NoteCommand::Add {
+            content,
+            title,
+            r#type,
+            tags,
+            session_id,
+        } => {
+            let session_id = session_or_env(client, session_id)?;
+            let body = json!({
+                "sessionId": session_id,
+                "title": title,
+                "type": r#type,
+                "content": content,
+                "tags": tags,
+            });
+            let raw = client.post_json_queued("/api/notes", &body).await?;
+            if raw.get("queued").is_some() {
+                // Already reported on stderr; there's no note to show yet.
+                if !human {
+                    println!("{}", serde_json::to_string_pretty(&raw)?);
+                }
+                return Ok(());
+            }
+            let resp: NoteResponse = serde_json::from_value(raw)?;
+            if human {
+                println!("Captured note {}", resp.note.id);
+            } else {
+                println!("{}", serde_json::to_string_pretty(&resp.note)?);
+            }
+        }
+        NoteCommand::List { folder_id, tag } => {
+            let folder_id = folder_id.or_else(|| std::env::var("RDV_PROJECT_ID").ok()).filter(|f| !f.is_empty());
+            let mut query: Vec<(&str, String)> = Vec::new();
+            query.extend(folder_id.map(|f| ("folderId", f)));
+            query.extend(tag.map(|t| ("tag", t)));
+            let resp: NotesResponse = client.get_with_query("/api/notes", &query).await?;
+            print_notes(&resp.notes, human, "No notes.")?;
+        }
+        NoteCommand::Show { id } => {
+            let resp: NoteResponse = client.get(&format!("/api/notes/{id}")).await?;
+            if human {
+                print_note(&resp.note);
+            } else {
+                println!("{}", serde_json::to_string_pretty(&resp.note)?);
+            }
+        }
+        NoteCommand::Edit {
+            id,
+            title,
+            content,
+            tags,
+        } => {
+            let mut body = serde_json::Map::new();
+            body.extend(title.map(|t| ("title".to_string(), json!(t))));
+            body.extend(content.map(|c| ("content".to_string(), json!(c))));
+            body.extend(tags.map(|t| ("tags".to_string(), json!(t))));
+            if body.is_empty() {
+                return Err("nothing to change; pass --title, --content or --tag".into());
+            }
+            let resp: NoteResponse = client.patch(&format!("/api/notes/{id}"), &serde_json::Value::from(body)).await?;
+            if human {
+                println!("Updated note {id}");
+            } else {
+                println!("{}", serde_json::to_string_pretty(&resp.note)?);
+            }
+        }
+        NoteCommand::Delete { id } => {
+            let res = client.delete(&format!("/api/notes/{id}")).await?;
+            if human {
+                println!("Deleted note {id}");
+            } else {
+                println!("{}", serde_json::to_string_pretty(&res)?);
+            }
+        }
+        NoteCommand::Search { query, folder_id, limit } => {
+            let folder_id = folder_id.or_else(|| std::env::var("RDV_PROJECT_ID").ok()).filter(|f| !f.is_empty());
+            let mut params = vec![("q", query), ("limit", limit.to_string())];
+            params.extend(folder_id.map(|f| ("folderId", f)));
+            let resp: NotesResponse = client.get_with_query("/api/notes", &params).await?;
+            print_notes(&resp.notes, human, "No matching notes.")?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{headline, Note};
+
+    fn note(title: Option<&str>, content: &str) -> Note {
+        serde_json::from_value(serde_json::json!({ "id": "n-1", "title": title, "content": content })).unwrap()
+    }
+
+    #[test]
+    fn headline_prefers_title_and_cuts_long_lines() {
+        assert_eq!(headline(&note(Some("Cache bug"), "details")), "Cache bug");
+        assert_eq!(headline(&note(None, "first line\nsecond")), "first line");
+        let long = headline(&note(None, &"y".repeat(80)));
+        assert_eq!(long.chars().count(), 60);
+        assert!(long.ends_with("..."));
+    }
+}
@@ -4,6 +4,7 @@ use serde_json::json;
 use tabled::{Table, Tabled};
 
 use crate::client::Client;
+use crate::time::format_timestamp;
 
 #[derive(Args)]
 pub struct NotificationArgs {
@@ -80,7 +81,7 @@ impl From<&Notification> for NotificationRow {
             notification_type: n.notification_type.clone().unwrap_or_default(),
             title: n.title.clone().unwrap_or_default(),
             read: if n.read.unwrap_or(false) { "yes".into() } else { "no".into() },
-            created_at: n.created_at.clone().unwrap_or_default(),
+            created_at: n.created_at.as_deref().map(format_timestamp).unwrap_or_default(),
         }
     }
 }
@@ -0,0 +1,180 @@
+use chrono::{Duration, Utc};
+use clap::Args;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::client::Client;
+use crate::time::format_timestamp;
+
+const PAUSE_PATH: &str = "/api/automation/pause";
+
+#[derive(Args)]
+pub struct PauseArgs {
+    /// Pause only this folder (default: the whole workspace)
+    #[arg(long)]
+    folder: Option<String>,
+    /// Resume automatically after this long (e.g. 30m, 2h, 1d, 1h30m)
+    #[arg(long = "for", value_name = "DURATION")]
+    duration: Option<String>,
+    /// Why automation is paused (logged with every skipped action)
+    #[arg(long)]
+    reason: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ResumeArgs {
+    /// Resume only this folder (default: the whole workspace)
+    #[arg(long)]
+    folder: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Pause {
+    pub folder_id: Option<String>,
+    pub folder_name: Option<String>,
+    pub paused_at: Option<String>,
+    /// When the pause lapses on its own; `None` means until resumed.
+    pub until: Option<String>,
+    pub reason: Option<String>,
+}
+
+impl Pause {
+    /// "until 2026-03-01 14:00:00 UTC (deploy freeze)" style suffix.
+    pub(crate) fn describe(&self) -> String {
+        let mut text = match self.until {
+            Some(ref u) => format!("until {}", format_timestamp(u)),
+            None => "until resumed".to_string(),
+        };
+        if let Some(ref r) = self.reason {
+            text.push_str(&format!(" ({r})"));
+        }
+        text
+    }
+}
+
+/// Current pause state: the workspace-wide pause, if any, plus paused folders.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct PauseState {
+    pub global: Option<Pause>,
+    #[serde(default)]
+    pub folders: Vec<Pause>,
+}
+
+/// Fetch pause state. Every failure, including a 404, is returned: an
+/// unreadable pause must not look like no pause.
+pub(crate) async fn fetch_state(client: &Client) -> Result<PauseState, Box<dyn std::error::Error>> {
+    client.get(PAUSE_PATH).await
+}
+
+/// Parse `30m`, `2h`, `1d`, `90s`, or combinations like `1h30m`.
+pub(crate) fn parse_duration(text: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration {text:?}; use e.g. 30m, 2h, 1d or 1h30m");
+    let mut total = Duration::zero();
+    let mut digits = String::new();
+    for c in text.trim().chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let n: i64 = digits.parse().map_err(|_| invalid())?;
+        digits.clear();
+        let part = match c {
+            's' => Duration::try_seconds(n),
+            'm' => Duration::try_minutes(n),
+            'h' => Duration::try_hours(n),
+            'd' => Duration::try_days(n),
+            _ => return Err(invalid()),
+        };
+        total = part
+            .and_then(|p| total.checked_add(&p))
+            .ok_or_else(|| format!("duration {text:?} is too long"))?;
+    }
+    if !digits.is_empty() || total <= Duration::zero() {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+pub async fn run_pause(args: PauseArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let until = args
+        .duration
+        .as_deref()
+        .map(parse_duration)
+        .transpose()?
+        .map(|d| (Utc::now() + d).to_rfc3339());
+    let body = json!({
+        "folderId": args.folder,
+        "until": until,
+        "reason": args.reason,
+    });
+    let result = client.post_json(PAUSE_PATH, &body).await?;
+    if human {
+        let scope = match args.folder {
+            Some(ref f) => format!("Automation paused for folder {f}"),
+            None => "All automation paused".to_string(),
+        };
+        let pause = Pause {
+            folder_id: args.folder,
+            folder_name: None,
+            paused_at: None,
+            until,
+            reason: args.reason,
+        };
+        println!("{} {}", scope.yellow().bold(), pause.describe());
+    } else {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    }
+    Ok(())
+}
+
+pub async fn run_resume(args: ResumeArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let result = match args.folder {
+        Some(ref f) => {
+            client
+                .delete_with_body(PAUSE_PATH, &json!({ "folderId": f }))
+                .await?
+        }
+        None => client.delete(PAUSE_PATH).await?,
+    };
+    if human {
+        match args.folder {
+            Some(f) => println!("Automation resumed for folder {f}"),
+            None => println!("Automation resumed"),
+        }
+    } else {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::parse_duration;
+
+    #[test]
+    fn parses_single_and_combined_units() {
+        assert_eq!(parse_duration("30m"), Ok(Duration::minutes(30)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::hours(2)));
+        assert_eq!(parse_duration("1d"), Ok(Duration::days(1)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::minutes(90)));
+    }
+
+    #[test]
+    fn rejects_missing_unit_or_zero() {
+        assert!(parse_duration("90").is_err());
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("2w").is_err());
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_without_panicking() {
+        assert!(parse_duration("999999999999999d").is_err());
+        assert!(parse_duration("9223372036854775807s").is_err());
+        assert!(parse_duration("99999999999999999999m").is_err());
+    }
+}
@@ -4,6 +4,7 @@ use serde_json::json;
 use tabled::{Table, Tabled};
 
 use crate::client::Client;
+use crate::time::format_timestamp;
 
 #[derive(Args)]
 pub struct PeerArgs {
@@ -146,7 +147,7 @@ impl From<&PeerMessage> for MessageRow {
             } else {
                 m.body.clone()
             },
-            time: m.created_at.clone(),
+            time: format_timestamp(&m.created_at),
         }
     }
 }
@@ -1,26 +1,155 @@
+use std::time::Duration;
+
 use clap::Args;
+use serde_json::json;
 
 use crate::client::Client;
+use crate::commands::completions;
+use crate::error::ApiError;
+use crate::follow::Reconnect;
+
+/// How often `--follow` re-captures the screen.
+const FOLLOW_POLL: Duration = Duration::from_secs(1);
 
 #[derive(Args)]
 pub struct ScreenArgs {
     /// Session ID to capture screen from
+    #[arg(add = completions::session_ids())]
     session_id: String,
+    /// Keep capturing and print lines as they appear
+    #[arg(long, short)]
+    follow: bool,
+    /// Only print the last N lines of the first capture
+    #[arg(long)]
+    lines: Option<usize>,
+    /// Only print lines containing this text
+    #[arg(long)]
+    grep: Option<String>,
 }
 
-pub async fn run(args: ScreenArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
-    let query = [("sessionId", args.session_id.as_str())];
+/// Capture lines without the blank rows below the cursor.
+fn screen_lines(content: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = content.lines().collect();
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Lines of `next` that weren't on screen in `prev`. The pane may have
+/// scrolled by some number of rows and then changed in place (a progress bar,
+/// a line being typed), so try every scroll offset, keep the one where the
+/// most rows of `next` still match `prev` from the top, and treat everything
+/// from the first differing row as new. No match at any offset means the pane
+/// was cleared or redrawn, and all of `next` is new.
+fn new_lines<'a>(prev: &[&str], next: &[&'a str]) -> Vec<&'a str> {
+    let matched = (0..prev.len())
+        .map(|scroll| {
+            prev[scroll..]
+                .iter()
+                .zip(next)
+                .take_while(|(a, b)| a == b)
+                .count()
+        })
+        .max()
+        .unwrap_or(0);
+    next[matched..].to_vec()
+}
+
+/// Print lines passing the filter: plain text for humans, otherwise one JSON
+/// object per line so `--json` stays machine-readable while streaming.
+fn print_lines(
+    session_id: &str,
+    lines: &[&str],
+    grep: Option<&str>,
+    human: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for line in lines {
+        if grep.is_some_and(|g| !line.contains(g)) {
+            continue;
+        }
+        if human {
+            println!("{line}");
+        } else {
+            println!("{}", serde_json::to_string(&json!({ "sessionId": session_id, "line": line }))?);
+        }
+    }
+    Ok(())
+}
+
+async fn capture(client: &Client, session_id: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let query = [("sessionId", session_id)];
     let result: serde_json::Value = client.get_with_query("/internal/screen", &query).await?;
+    Ok(result.get("content").and_then(|v| v.as_str()).unwrap_or("").to_string())
+}
 
-    if human {
-        let content = result
-            .get("content")
-            .and_then(|v| v.as_str())
-            .unwrap_or("");
-        println!("{content}");
-    } else {
-        println!("{}", serde_json::to_string_pretty(&result)?);
+pub async fn run(args: ScreenArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if !args.follow && args.lines.is_none() && args.grep.is_none() {
+        let query = [("sessionId", args.session_id.as_str())];
+        let result: serde_json::Value = client.get_with_query("/internal/screen", &query).await?;
+        if human {
+            let content = result
+                .get("content")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            println!("{content}");
+        } else {
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+        return Ok(());
     }
 
-    Ok(())
+    let grep = args.grep.as_deref();
+    let first = capture(client, &args.session_id).await?;
+    let mut prev: Vec<String> = screen_lines(&first).into_iter().map(String::from).collect();
+    let skip = args.lines.map_or(0, |n| prev.len().saturating_sub(n));
+    let shown: Vec<&str> = prev.iter().skip(skip).map(String::as_str).collect();
+    print_lines(&args.session_id, &shown, grep, human)?;
+    if !args.follow {
+        return Ok(());
+    }
+
+    let mut reconnect = Reconnect::default();
+    loop {
+        tokio::time::sleep(FOLLOW_POLL).await;
+        let content = match capture(client, &args.session_id).await {
+            Ok(c) => c,
+            Err(e) if !ApiError::from_boxed(e.as_ref()).is_some_and(ApiError::is_retryable) => return Err(e),
+            Err(e) => {
+                let hint = ApiError::from_boxed(e.as_ref()).and_then(ApiError::retry_after);
+                reconnect.wait(&e, hint).await;
+                continue;
+            }
+        };
+        reconnect.connected();
+        let next = screen_lines(&content);
+        let old: Vec<&str> = prev.iter().map(String::as_str).collect();
+        print_lines(&args.session_id, &new_lines(&old, &next), grep, human)?;
+        prev = next.into_iter().map(String::from).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{new_lines, screen_lines};
+
+    #[test]
+    fn scrolled_screens_yield_only_the_new_tail() {
+        assert_eq!(new_lines(&["a", "b", "c"], &["b", "c", "d", "e"]), ["d", "e"]);
+        assert_eq!(new_lines(&["a", "b"], &["a", "b"]), Vec::<&str>::new());
+        assert_eq!(new_lines(&["a", "b"], &["x", "y"]), ["x", "y"]);
+        assert_eq!(new_lines(&[], &["a"]), ["a"]);
+    }
+
+    #[test]
+    fn in_place_changes_yield_only_the_changed_rows() {
+        assert_eq!(new_lines(&["$ build", "[=>  ] 40%"], &["$ build", "[==> ] 60%"]), ["[==> ] 60%"]);
+        assert_eq!(new_lines(&["a", "b", "50%"], &["b", "100%", "done"]), ["100%", "done"]);
+        assert_eq!(new_lines(&["a", "b", "a", "b"], &["a", "b", "a", "b", "c"]), ["c"]);
+    }
+
+    #[test]
+    fn trailing_blank_rows_are_ignored() {
+        assert_eq!(screen_lines("$ ls\nsrc\n\n   \n"), ["$ ls", "src"]);
+    }
 }
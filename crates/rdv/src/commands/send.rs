@@ -1,7 +1,12 @@
+use std::time::Duration;
+
 use clap::{Args, Subcommand};
+use tokio::time::Instant;
 use serde_json::json;
 
 use crate::client::Client;
+use crate::commands::completions;
+use crate::provider::Provider;
 
 #[derive(Args)]
 pub struct SendArgs {
@@ -14,7 +19,12 @@ enum SendCommand {
     /// Send text to a terminal session PTY
     Text {
         /// Session ID to send text to
+        #[arg(add = completions::session_ids())]
         session_id: String,
+        /// Send as a prompt to the session's agent: wait until its CLI has
+        /// finished booting, and escape newlines so they don't submit early
+        #[arg(long)]
+        prompt: bool,
         /// Text to send (joined with spaces if multiple args)
         #[arg(trailing_var_arg = true, required = true)]
         text: Vec<String>,
@@ -22,18 +32,63 @@ enum SendCommand {
     /// Send a keystroke to a terminal session
     Key {
         /// Session ID to send keystroke to
+        #[arg(add = completions::session_ids())]
         session_id: String,
         /// Key name (Enter, C-c, Tab, Escape, Up, Down, etc.)
         key: String,
     },
 }
 
+/// How long `--prompt` waits for the agent CLI to come up.
+const READY_TIMEOUT: Duration = Duration::from_secs(60);
+const READY_POLL: Duration = Duration::from_secs(1);
+
+/// Wait until the session's agent CLI is accepting input and return its
+/// provider. Sessions without a known provider are sent to straight away.
+async fn wait_until_ready(client: &Client, session_id: &str) -> Result<Option<Provider>, Box<dyn std::error::Error>> {
+    let session: serde_json::Value = client.get(&format!("/api/sessions/{session_id}")).await?;
+    let Some(provider) = session
+        .get("agentProvider")
+        .and_then(|v| v.as_str())
+        .and_then(Provider::from_name)
+    else {
+        return Ok(None);
+    };
+    let deadline = Instant::now() + READY_TIMEOUT;
+    loop {
+        let screen: serde_json::Value = client
+            .get_with_query("/internal/screen", &[("sessionId", session_id)])
+            .await?;
+        if provider.is_ready(screen.get("content").and_then(|v| v.as_str()).unwrap_or("")) {
+            return Ok(Some(provider));
+        }
+        if Instant::now() >= deadline {
+            return Err(format!(
+                "{provider} in session {session_id} not ready after {}s",
+                READY_TIMEOUT.as_secs()
+            )
+            .into());
+        }
+        tokio::time::sleep(READY_POLL).await;
+    }
+}
+
 pub async fn run(args: SendArgs, client: &Client) -> Result<(), Box<dyn std::error::Error>> {
     match args.command {
-        SendCommand::Text { session_id, text } => {
+        SendCommand::Text {
+            session_id,
+            prompt,
+            text,
+        } => {
+            let mut text = text.join(" ");
+            if prompt {
+                if let Some(provider) = wait_until_ready(client, &session_id).await? {
+                    text = provider.escape_prompt(&text);
+                }
+            }
             let body = json!({
                 "sessionId": session_id,
-                "text": text.join(" "),
+                "text": text,
             });
             client.post_json("/internal/pty-write", &body).await?;
         }
@@ -1,9 +1,14 @@
+use chrono::Utc;
+use clap::builder::NonEmptyStringValueParser;
 use clap::{Args, Subcommand};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use tabled::{Table, Tabled};
 
 use crate::client::Client;
+use crate::commands::completions;
+use crate::commands::pause::parse_duration;
+use crate::time::parse_timestamp;
 
 #[derive(Args)]
 pub struct SessionArgs {
@@ -14,8 +19,16 @@ pub struct SessionArgs {
 #[derive(Subcommand)]
 enum SessionCommand {
     /// List all sessions
-    List,
+    List {
+        /// Return at most this many sessions (one page) instead of all
+        #[arg(long)]
+        limit: Option<u32>,
+        /// Resume from the `nextCursor` of a previous page
+        #[arg(long)]
+        cursor: Option<String>,
+    },
     /// Create a new session
+    #[command(group(clap::ArgGroup::new("readiness").args(["ready_port", "ready_output"])))]
     Create {
         /// Session name
         #[arg(long)]
@@ -29,25 +42,62 @@ enum SessionCommand {
         /// Terminal type (shell, agent, browser)
         #[arg(long)]
         r#type: Option<String>,
+        /// Wait for this session to be ready before creating (repeatable;
+        /// needs --ready-port or --ready-output)
+        #[arg(long = "depends-on", requires = "readiness", add = completions::session_ids())]
+        depends_on: Vec<String>,
+        /// Ready once something listens on this local port
+        #[arg(long, requires = "depends_on")]
+        ready_port: Option<u16>,
+        /// Ready once each dependency's screen matches this regex
+        #[arg(long, requires = "depends_on")]
+        ready_output: Option<String>,
+        /// Give up waiting for dependencies after this long
+        #[arg(long, default_value = "5m", value_parser = parse_duration)]
+        ready_timeout: chrono::Duration,
+        /// Key that makes a repeated create return the first session instead
+        /// of a second one (default: fresh per invocation)
+        #[arg(long)]
+        idempotency_key: Option<String>,
     },
     /// Close (delete) a session
     Close {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         id: String,
     },
     /// Suspend a session
     Suspend {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         id: String,
     },
     /// Resume a suspended session
     Resume {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         id: String,
     },
+    /// Close every session matching the filters
+    #[command(group(clap::ArgGroup::new("filter").required(true).multiple(true).args(["folder_id", "status", "idle"])))]
+    Prune {
+        /// Only sessions in this folder
+        #[arg(long, add = completions::folder_ids(), value_parser = NonEmptyStringValueParser::new())]
+        folder_id: Option<String>,
+        /// Only sessions with this status, e.g. suspended
+        #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+        status: Option<String>,
+        /// Only sessions idle at least this long: minutes, or a duration like 2h
+        #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+        idle: Option<String>,
+        /// List the sessions that would be closed without closing them
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Execute a command in a session (fire-and-forget)
     Exec {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         id: String,
         /// Command to execute
         cmd: String,
@@ -55,6 +105,7 @@ enum SessionCommand {
     /// Get git status for a session's working directory
     GitStatus {
         /// Session ID
+        #[arg(add = completions::session_ids())]
         id: String,
     },
     /// Set session title (kebab-case, 3-5 words)
@@ -69,17 +120,43 @@ struct Session {
     id: String,
     name: Option<String>,
     status: Option<String>,
-    #[serde(rename = "folderId")]
+    #[serde(rename = "folderId", alias = "projectId")]
     folder_id: Option<String>,
+    #[serde(rename = "lastActivityAt", default, skip_serializing_if = "Option::is_none")]
+    last_activity_at: Option<String>,
     #[serde(rename = "workingDirectory")]
     working_directory: Option<String>,
     #[serde(rename = "terminalType")]
     terminal_type: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct SessionsResponse {
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PruneReport {
     sessions: Vec<Session>,
+    dry_run: bool,
+}
+
+/// Whether `s` is in `folder_id` (when given) and was last active before
+/// `idle_before` (when given). A session with no activity time is never idle.
+fn prune_match(s: &Session, folder_id: Option<&str>, idle_before: Option<chrono::DateTime<Utc>>) -> bool {
+    let in_folder = folder_id.is_none_or(|f| s.folder_id.as_deref() == Some(f));
+    let idle = idle_before.is_none_or(|cutoff| {
+        s.last_activity_at
+            .as_deref()
+            .and_then(parse_timestamp)
+            .is_some_and(|t| t < cutoff)
+    });
+    in_folder && idle
+}
+
+/// `--idle` as whole minutes; a bare number is already minutes.
+fn idle_minutes(idle: &str) -> Result<i64, String> {
+    if let Ok(n) = idle.parse::<i64>() {
+        return if n > 0 { Ok(n) } else { Err("--idle must be positive".into()) };
+    }
+    let d = parse_duration(idle)?;
+    Ok(d.num_minutes().max(1))
 }
 
 #[derive(Tabled)]
@@ -108,14 +185,71 @@ impl From<&Session> for SessionRow {
     }
 }
 
+/// What `--depends-on` waits for before the session is created.
+enum Readiness {
+    Port(u16),
+    Output(regex::Regex),
+}
+
+/// How often dependencies are re-checked while waiting.
+const READY_POLL: std::time::Duration = std::time::Duration::from_secs(2);
+
+async fn dependency_ready(client: &Client, id: &str, check: &Readiness) -> Result<bool, Box<dyn std::error::Error>> {
+    match check {
+        Readiness::Port(port) => Ok(tokio::net::TcpStream::connect(("127.0.0.1", *port)).await.is_ok()),
+        Readiness::Output(pattern) => {
+            let query = [("sessionId", id)];
+            let screen: serde_json::Value = client.get_with_query("/internal/screen", &query).await?;
+            Ok(pattern.is_match(screen["content"].as_str().unwrap_or("")))
+        }
+    }
+}
+
+/// Poll each dependency until its readiness check passes, or fail once
+/// `timeout` has passed. Unknown session IDs fail straight away.
+async fn wait_for_dependencies(
+    client: &Client,
+    ids: &[String],
+    check: &Readiness,
+    timeout: chrono::Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let deadline = Utc::now() + timeout;
+    for id in ids {
+        let _: serde_json::Value = client.get(&format!("/api/sessions/{id}")).await?;
+        while !dependency_ready(client, id, check).await? {
+            if Utc::now() >= deadline {
+                return Err(format!("dependency {id} was not ready within {}s", timeout.num_seconds()).into());
+            }
+            eprintln!("Waiting for {id}...");
+            tokio::time::sleep(READY_POLL).await;
+        }
+    }
+    Ok(())
+}
+
 pub async fn run(args: SessionArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
     match args.command {
-        SessionCommand::List => {
-            let resp: SessionsResponse = client.get("/api/sessions").await?;
-            let sessions = resp.sessions;
+        SessionCommand::List { limit, cursor } => {
+            let paged = limit.is_some() || cursor.is_some();
+            let (sessions, next_cursor): (Vec<Session>, Option<String>) = if paged {
+                let page = client
+                    .get_page("/api/sessions", "sessions", &[], limit, cursor.as_deref())
+                    .await?;
+                (page.items, page.next_cursor)
+            } else {
+                (client.get_all_pages("/api/sessions", "sessions", &[]).await?, None)
+            };
             if human {
                 let rows: Vec<SessionRow> = sessions.iter().map(SessionRow::from).collect();
                 println!("{}", Table::new(rows));
+                if let Some(ref c) = next_cursor {
+                    println!("More sessions: --cursor {c}");
+                }
+            } else if paged {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({ "sessions": sessions, "nextCursor": next_cursor }))?
+                );
             } else {
                 println!("{}", serde_json::to_string_pretty(&json!(sessions))?);
             }
@@ -125,7 +259,20 @@ pub async fn run(args: SessionArgs, client: &Client, human: bool) -> Result<(),
             folder_id,
             working_dir,
             r#type,
+            depends_on,
+            ready_port,
+            ready_output,
+            ready_timeout,
+            idempotency_key,
         } => {
+            if !depends_on.is_empty() {
+                let check = match (ready_port, ready_output) {
+                    (Some(port), _) => Readiness::Port(port),
+                    (None, Some(pattern)) => Readiness::Output(regex::Regex::new(&pattern)?),
+                    (None, None) => unreachable!("clap requires a readiness check with --depends-on"),
+                };
+                wait_for_dependencies(client, &depends_on, &check, ready_timeout).await?;
+            }
             let mut body = json!({});
             if let Some(n) = name {
                 body["name"] = json!(n);
@@ -139,7 +286,9 @@ pub async fn run(args: SessionArgs, client: &Client, human: bool) -> Result<(),
             if let Some(t) = r#type {
                 body["terminalType"] = json!(t);
             }
-            let result: serde_json::Value = client.post_json("/api/sessions", &body).await?;
+            let result: serde_json::Value = client
+                .post_json_idempotent("/api/sessions", &body, idempotency_key.as_deref())
+                .await?;
             println!("{}", serde_json::to_string_pretty(&result)?);
         }
         SessionCommand::Close { id } => {
@@ -154,6 +303,54 @@ pub async fn run(args: SessionArgs, client: &Client, human: bool) -> Result<(),
             let result = client.post_empty(&format!("/api/sessions/{id}/resume")).await?;
             println!("{}", serde_json::to_string_pretty(&result)?);
         }
+        SessionCommand::Prune {
+            folder_id,
+            status,
+            idle,
+            dry_run,
+        } => {
+            // Select on the client and close one by one, so a dry run never
+            // depends on the server honouring a flag.
+            let idle_before = idle
+                .as_deref()
+                .map(idle_minutes)
+                .transpose()?
+                .map(|m| Utc::now() - chrono::Duration::minutes(m));
+            let query: Vec<(&str, String)> = status.into_iter().map(|s| ("status", s)).collect();
+            let mut matched: Vec<Session> = client.get_all_pages("/api/sessions", "sessions", &query).await?;
+            matched.retain(|s| prune_match(s, folder_id.as_deref(), idle_before));
+            let (sessions, failed) = if dry_run {
+                (matched, Vec::new())
+            } else {
+                let (mut closed, mut failed) = (Vec::new(), Vec::new());
+                for s in matched {
+                    match client.delete(&format!("/api/sessions/{}", s.id)).await {
+                        Ok(_) => closed.push(s),
+                        Err(e) => failed.push(format!("{}: {e}", s.id)),
+                    }
+                }
+                (closed, failed)
+            };
+            let report = PruneReport { sessions, dry_run };
+            if human {
+                if report.sessions.is_empty() {
+                    println!("No sessions match.");
+                } else {
+                    let rows: Vec<SessionRow> = report.sessions.iter().map(SessionRow::from).collect();
+                    println!("{}", Table::new(rows));
+                    let verb = if report.dry_run { "Would close" } else { "Closed" };
+                    println!("{verb} {} session(s)", report.sessions.len());
+                }
+            } else {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            }
+            if !failed.is_empty() {
+                for f in &failed {
+                    eprintln!("{f}");
+                }
+                return Err(format!("{} session(s) could not be closed", failed.len()).into());
+            }
+        }
         SessionCommand::Exec { id, cmd } => {
             let body = json!({ "command": cmd });
             let result: serde_json::Value = client.post_json(&format!("/api/sessions/{id}/exec"), &body).await?;
@@ -172,7 +369,7 @@ pub async fn run(args: SessionArgs, client: &Client, human: bool) -> Result<(),
             }
             // Validate 3-5 hyphen-separated words
             let word_count = title.split('-').count();
-            if word_count < 3 || word_count > 5 {
+            if !(3..=5).contains(&word_count) {
                 return Err("title must have 3-5 hyphen-separated words".into());
             }
             // Ensure no empty segments (e.g. leading/trailing/double hyphens)
@@ -193,3 +390,31 @@ pub async fn run(args: SessionArgs, client: &Client, human: bool) -> Result<(),
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{idle_minutes, prune_match, Session};
+
+    #[test]
+    fn prune_matches_folder_and_idle_cutoff() {
+        let s: Session = serde_json::from_value(serde_json::json!({
+            "id": "s-1", "projectId": "f-1", "lastActivityAt": "2026-03-01T10:00:00Z"
+        }))
+        .unwrap();
+        let cutoff = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+        assert!(prune_match(&s, Some("f-1"), Some(cutoff)));
+        assert!(!prune_match(&s, Some("f-2"), None));
+        assert!(!prune_match(&s, None, Some(Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap())));
+    }
+
+    #[test]
+    fn idle_takes_minutes_or_durations() {
+        assert_eq!(idle_minutes("120"), Ok(120));
+        assert_eq!(idle_minutes("2h"), Ok(120));
+        assert_eq!(idle_minutes("1d"), Ok(1440));
+        assert!(idle_minutes("0").is_err());
+        assert!(idle_minutes("soon").is_err());
+    }
+}
@@ -4,6 +4,10 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::client::Client;
+use crate::commands::usage;
+use crate::commands::pause;
+use crate::error::ApiError;
+use crate::time::{format_relative_time, format_timestamp};
 
 #[derive(Args)]
 pub struct StatusArgs {
@@ -71,6 +75,16 @@ fn read_deploy_state() -> Option<DeployState> {
     serde_json::from_str(&contents).ok()
 }
 
+/// Optional dashboard sections: a server without the route (404) has nothing
+/// to show, but any other failure is reported rather than read as empty.
+fn optional<T: Default>(result: Result<T, Box<dyn std::error::Error>>) -> Result<T, String> {
+    match result {
+        Ok(v) => Ok(v),
+        Err(e) if matches!(ApiError::from_boxed(e.as_ref()), Some(ApiError::NotFound(_))) => Ok(T::default()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
 fn read_server_mode() -> Option<String> {
     let base_dir = std::env::var("RDV_DATA_DIR")
         .map(std::path::PathBuf::from)
@@ -84,33 +98,6 @@ fn read_server_mode() -> Option<String> {
     std::fs::read_to_string(mode_file).ok().map(|s| s.trim().to_string())
 }
 
-fn format_relative_time(iso_time: &str) -> String {
-    // Parse ISO 8601 timestamp and compute relative time
-    let Ok(deployed) = chrono::DateTime::parse_from_rfc3339(iso_time) else {
-        return iso_time.to_string();
-    };
-    let now = chrono::Utc::now();
-    let duration = now.signed_duration_since(deployed);
-
-    let secs = duration.num_seconds();
-    if secs < 60 {
-        format!("{}s ago", secs)
-    } else if secs < 3600 {
-        format!("{}m ago", secs / 60)
-    } else if secs < 86400 {
-        let hours = secs / 3600;
-        let mins = (secs % 3600) / 60;
-        if mins > 0 {
-            format!("{}h {}m ago", hours, mins)
-        } else {
-            format!("{}h ago", hours)
-        }
-    } else {
-        let days = secs / 86400;
-        format!("{}d ago", days)
-    }
-}
-
 pub async fn run(args: StatusArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
     match args.command {
         Some(StatusCommand::Report { status }) => {
@@ -124,9 +111,22 @@ pub async fn run(args: StatusArgs, client: &Client, human: bool) -> Result<(), B
         }
         None => {
             // Dashboard: show sessions + tasks summary
+            let paused = pause::fetch_state(client).await;
             if human {
                 println!("{}", "Remote Dev Status".bold().underline());
                 println!();
+                match paused {
+                    Ok(ref paused) => {
+                        if let Some(ref p) = paused.global {
+                            println!("{} {}", "AUTOMATION PAUSED".red().bold(), p.describe());
+                        }
+                        for p in &paused.folders {
+                            let folder = p.folder_name.as_deref().or(p.folder_id.as_deref()).unwrap_or("?");
+                            println!("{} {} {}", "Paused:".yellow().bold(), folder, p.describe());
+                        }
+                    }
+                    Err(ref e) => println!("{}: {} ({e})", "Pause state".bold(), "unavailable".yellow()),
+                }
             }
 
             // Server info
@@ -154,6 +154,9 @@ pub async fn run(args: StatusArgs, client: &Client, human: bool) -> Result<(), B
                 None
             };
 
+            // Usage is optional: older servers have no Claude usage route.
+            let usage = optional(usage::fetch(client).await);
+
             if human {
                 // Server section
                 if let Some(ref m) = mode {
@@ -174,7 +177,7 @@ pub async fn run(args: StatusArgs, client: &Client, human: bool) -> Result<(), B
                     println!(
                         "{}: {} (slot: {})",
                         "Build".bold(),
-                        d.deployed_at.dimmed(),
+                        format_timestamp(&d.deployed_at).dimmed(),
                         d.active_slot.cyan(),
                     );
                 }
@@ -194,6 +197,13 @@ pub async fn run(args: StatusArgs, client: &Client, human: bool) -> Result<(), B
                         done,
                     );
                 }
+                match usage {
+                    Ok(ref u) if !u.profiles.is_empty() => {
+                        println!("{}: {}", "Usage".bold(), u.describe());
+                    }
+                    Ok(_) => {}
+                    Err(ref e) => println!("{}: {} ({e})", "Usage".bold(), "unavailable".yellow()),
+                }
             } else {
                 let mut dashboard = json!({
                     "sessions": {
@@ -209,6 +219,18 @@ pub async fn run(args: StatusArgs, client: &Client, human: bool) -> Result<(), B
                         "done": done,
                     });
                 }
+                match paused {
+                    Ok(ref p) if p.global.is_some() || !p.folders.is_empty() => {
+                        dashboard["automationPause"] = json!(p);
+                    }
+                    Ok(_) => {}
+                    Err(ref e) => dashboard["automationPause"] = json!({ "error": e.to_string() }),
+                }
+                match usage {
+                    Ok(ref u) if !u.profiles.is_empty() => dashboard["usage"] = json!(u),
+                    Ok(_) => {}
+                    Err(ref e) => dashboard["usage"] = json!({ "error": e }),
+                }
                 if let Some(ref m) = mode {
                     dashboard["server"] = json!({ "mode": m });
                 }
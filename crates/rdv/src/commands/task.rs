@@ -0,0 +1,295 @@
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tabled::{Table, Tabled};
+
+use crate::client::Client;
+use crate::commands::completions;
+use crate::commands::worktree::resolve_session_id;
+use crate::git::{self, Repo};
+
+#[derive(Args)]
+pub struct TaskArgs {
+    #[command(subcommand)]
+    command: TaskCommand,
+}
+
+#[derive(Subcommand)]
+enum TaskCommand {
+    /// Queue a task for the folder's sessions to pick up
+    Create {
+        /// Short title
+        title: String,
+        /// Longer description or acceptance criteria
+        #[arg(long)]
+        description: Option<String>,
+        /// Folder ID (defaults to RDV_PROJECT_ID)
+        #[arg(long, add = completions::folder_ids())]
+        folder_id: Option<String>,
+        /// Priority; boards sort critical first
+        #[arg(long, default_value = "medium", value_parser = ["critical", "high", "medium", "low"])]
+        priority: String,
+        /// Task that must be done first (repeatable)
+        #[arg(long = "blocked-by", value_name = "TASK_ID")]
+        blocked_by: Vec<String>,
+    },
+    /// List tasks in a folder
+    List {
+        /// Folder ID (defaults to RDV_PROJECT_ID)
+        #[arg(long, add = completions::folder_ids())]
+        folder_id: Option<String>,
+        /// Only show tasks in this state
+        #[arg(long, value_parser = ["open", "in_progress", "done", "cancelled"])]
+        status: Option<String>,
+    },
+    /// Start an open task and make a session its owner
+    Claim {
+        /// Task ID
+        id: String,
+        /// Session to assign (defaults to RDV_SESSION_ID)
+        #[arg(long)]
+        session_id: Option<String>,
+    },
+    /// Mark a task done
+    Complete {
+        /// Task ID
+        id: String,
+        /// What was done, kept in the task's metadata
+        #[arg(long)]
+        summary: Option<String>,
+    },
+    /// Commit the worktree's changes, push its branch and open a pull request
+    Submit {
+        /// Task the work is for; its title becomes the commit subject
+        id: String,
+        /// Commit message (default: generated from the task)
+        #[arg(long, short)]
+        message: Option<String>,
+        /// Branch the pull request targets (default: origin's default branch)
+        #[arg(long)]
+        base: Option<String>,
+        /// Open the pull request as a draft
+        #[arg(long)]
+        draft: bool,
+        /// Push without opening a pull request
+        #[arg(long, conflicts_with = "draft")]
+        no_pr: bool,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Task {
+    id: String,
+    title: String,
+    description: Option<String>,
+    project_id: Option<String>,
+    session_id: Option<String>,
+    /// `open`, `in_progress`, `done`, or `cancelled`.
+    status: String,
+    /// `critical`, `high`, `medium`, or `low`.
+    priority: String,
+    /// Session (or person) working on the task.
+    owner: Option<String>,
+    #[serde(default)]
+    blocked_by: Vec<String>,
+    #[serde(default)]
+    metadata: serde_json::Map<String, serde_json::Value>,
+    created_at: String,
+}
+
+#[derive(Tabled)]
+struct TaskRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Title")]
+    title: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Priority")]
+    priority: String,
+    #[tabled(rename = "Owner")]
+    owner: String,
+    #[tabled(rename = "Blocked By")]
+    blocked_by: String,
+}
+
+impl From<&Task> for TaskRow {
+    fn from(t: &Task) -> Self {
+        Self {
+            id: t.id.clone(),
+            title: t.title.clone(),
+            status: t.status.clone(),
+            priority: t.priority.clone(),
+            owner: t.owner.clone().unwrap_or_default(),
+            blocked_by: t.blocked_by.join(", "),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SubmitResult {
+    task_id: String,
+    branch: String,
+    base: String,
+    /// New commit, when there were uncommitted changes.
+    commit: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    files: Vec<String>,
+    pr_url: Option<String>,
+}
+
+async fn submit(
+    client: &Client,
+    id: String,
+    message: Option<String>,
+    base: Option<String>,
+    draft: bool,
+    no_pr: bool,
+) -> Result<SubmitResult, Box<dyn std::error::Error>> {
+    let repo = Repo::open(&std::env::current_dir()?).await?;
+    let branch = repo.current_branch().await?;
+    git::check_not_protected(&branch)?;
+    let base = match base {
+        Some(b) => b,
+        None => repo.default_branch().await,
+    };
+    if branch == base {
+        return Err(format!("{branch} is the base branch; submit from the session branch").into());
+    }
+
+    let task: Task = client.get(&format!("/api/tasks/{id}")).await?;
+    let message = message.unwrap_or_else(|| git::task_commit_message(&task.id, &task.title, task.description.as_deref()));
+
+    repo.stage_all().await?;
+    // Commit what was staged by hand too; report everything going in.
+    let files = repo.staged().await?;
+    let commit = if files.is_empty() { None } else { Some(repo.commit(&message).await?) };
+    if commit.is_none() && repo.commits_ahead(&base).await? == 0 {
+        return Err(format!("nothing to submit: no changes and no commits ahead of {base}").into());
+    }
+    repo.push(&branch).await?;
+
+    let pr_url = if no_pr {
+        None
+    } else {
+        let body = format!(
+            "{}\n\nTask: {}",
+            task.description.as_deref().unwrap_or("").trim(),
+            task.id
+        );
+        Some(repo.open_pr(&base, &branch, &task.title, body.trim_start(), draft).await?)
+    };
+    Ok(SubmitResult {
+        task_id: task.id,
+        branch,
+        base,
+        commit,
+        files,
+        pr_url,
+    })
+}
+
+/// Resolve the folder to act on: explicit flag, else `RDV_PROJECT_ID`.
+fn resolve_folder_id(folder_id: Option<String>) -> Result<String, Box<dyn std::error::Error>> {
+    folder_id
+        .or_else(|| std::env::var("RDV_PROJECT_ID").ok())
+        .filter(|f| !f.is_empty())
+        .ok_or_else(|| "no --folder-id given and RDV_PROJECT_ID is not set".into())
+}
+
+pub async fn run(args: TaskArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
+    match args.command {
+        TaskCommand::Create {
+            title,
+            description,
+            folder_id,
+            priority,
+            blocked_by,
+        } => {
+            let folder_id = resolve_folder_id(folder_id)?;
+            let body = json!({
+                "projectId": folder_id,
+                "title": title,
+                "description": description,
+                "priority": priority,
+                "blockedBy": blocked_by,
+            });
+            let task: Task = serde_json::from_value(client.post_json("/api/tasks", &body).await?)?;
+            if human {
+                println!("Queued task {}: {}", task.id, task.title);
+            } else {
+                println!("{}", serde_json::to_string_pretty(&task)?);
+            }
+        }
+        TaskCommand::List { folder_id, status } => {
+            let folder_id = resolve_folder_id(folder_id)?;
+            let mut tasks: Vec<Task> = client.get_with_query("/api/tasks", &[("folderId", &folder_id)]).await?;
+            // The route has no status filter.
+            if let Some(ref s) = status {
+                tasks.retain(|t| &t.status == s);
+            }
+            if human {
+                if tasks.is_empty() {
+                    println!("No tasks.");
+                } else {
+                    let rows: Vec<TaskRow> = tasks.iter().map(TaskRow::from).collect();
+                    println!("{}", Table::new(rows));
+                }
+            } else {
+                println!("{}", serde_json::to_string_pretty(&tasks)?);
+            }
+        }
+        TaskCommand::Claim { id, session_id } => {
+            let session_id = resolve_session_id(session_id, client)?;
+            let body = json!({ "status": "in_progress", "owner": session_id });
+            let task: Task = client.patch(&format!("/api/tasks/{id}"), &body).await?;
+            if human {
+                println!("Claimed task {}: {}", task.id, task.title);
+            } else {
+                println!("{}", serde_json::to_string_pretty(&task)?);
+            }
+        }
+        TaskCommand::Complete { id, summary } => {
+            let path = format!("/api/tasks/{id}");
+            let mut body = json!({ "status": "done" });
+            if let Some(summary) = summary {
+                // PATCH replaces metadata wholesale, so merge into what is there.
+                let current: Task = client.get(&path).await?;
+                let mut metadata = current.metadata;
+                metadata.insert("summary".into(), json!(summary));
+                body["metadata"] = metadata.into();
+            }
+            let task: Task = client.patch(&path, &body).await?;
+            if human {
+                println!("{}", format!("Completed task {}: {}", task.id, task.title).green());
+            } else {
+                println!("{}", serde_json::to_string_pretty(&task)?);
+            }
+        }
+        TaskCommand::Submit {
+            id,
+            message,
+            base,
+            draft,
+            no_pr,
+        } => {
+            let result = submit(client, id, message, base, draft, no_pr).await?;
+            if human {
+                if let Some(ref c) = result.commit {
+                    println!("Committed {c} ({} file(s))", result.files.len());
+                }
+                println!("Pushed {}", result.branch);
+                match result.pr_url {
+                    Some(ref url) => println!("{}", format!("Opened PR into {}: {url}", result.base).green()),
+                    None => println!("No PR opened (--no-pr)."),
+                }
+            } else {
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            }
+        }
+    }
+    Ok(())
+}
@@ -3,6 +3,7 @@ use serde::Deserialize;
 use serde_json::json;
 
 use crate::client::Client;
+use crate::provider::Provider;
 
 #[derive(Debug, Deserialize)]
 struct SessionsResponse {
@@ -25,8 +26,8 @@ enum TeamsCommand {
         /// Number of agent sessions to create
         #[arg(long, default_value = "2")]
         count: usize,
-        /// Agent provider (claude, codex, gemini)
-        #[arg(long, default_value = "claude")]
+        /// Agent provider
+        #[arg(long, default_value = "claude", value_parser = Provider::NAMES)]
         provider: String,
         /// Name prefix for sessions
         #[arg(long)]
@@ -90,7 +91,7 @@ pub async fn run(args: TeamsArgs, client: &Client, human: bool) -> Result<(), Bo
                     body["parentSessionId"] = json!(parent_id);
                 }
 
-                let result: serde_json::Value = client.post_json("/api/sessions", &body).await?;
+                let result: serde_json::Value = client.post_json_idempotent("/api/sessions", &body, None).await?;
                 let session_id = result["id"].as_str().unwrap_or("unknown");
                 let session_name = result["name"].as_str().unwrap_or(&name);
 
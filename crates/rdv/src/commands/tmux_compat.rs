@@ -33,7 +33,7 @@ fn passthrough_tmux(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
 
 /// Resolve a tmux target to an rdv session ID if it matches rdv patterns.
 /// Matches `rdv-<uuid>` prefixed names or bare UUIDs (8-4-4-4-12 hex format).
-fn resolve_session_id(target: &str) -> Option<String> {
+pub(crate) fn resolve_session_id(target: &str) -> Option<String> {
     if let Some(uuid) = target.strip_prefix("rdv-") {
         Some(uuid.to_string())
     } else if is_uuid(target) {
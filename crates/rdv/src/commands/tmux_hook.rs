@@ -0,0 +1,114 @@
+//! `rdv _hook`: the callback tmux runs from its own hooks, so the server
+//! learns about pane activity and exits as they happen instead of polling
+//! `last_activity_at`.
+
+use clap::{Args, Subcommand};
+
+use crate::client::Client;
+use crate::commands::tmux_compat::resolve_session_id;
+use crate::process::{self, RunOptions};
+
+/// tmux hooks that report to the server.
+const EVENTS: [&str; 3] = ["alert-activity", "pane-died", "client-detached"];
+
+/// Array index our hooks occupy, so `set-hook` leaves the user's own
+/// hooks for the same events alone.
+const HOOK_INDEX: u32 = 90;
+
+/// tmux user option holding the global `monitor-activity` value from before
+/// `install` turned it on, so `uninstall` can put it back.
+const SAVED_MONITOR_ACTIVITY: &str = "@rdv-saved-monitor-activity";
+
+#[derive(Args)]
+pub struct TmuxHookArgs {
+    #[command(subcommand)]
+    command: TmuxHookCommand,
+}
+
+#[derive(Subcommand)]
+enum TmuxHookCommand {
+    /// Register the hooks on the running tmux server
+    Install,
+    /// Remove the hooks registered by `install`
+    Uninstall,
+    /// Report one hook firing (run by tmux)
+    Fire {
+        #[arg(value_parser = EVENTS)]
+        event: String,
+        /// tmux session name; only `rdv-<id>` sessions are reported
+        session: String,
+    },
+}
+
+/// The tmux command a hook runs. `-b` keeps tmux from blocking on the
+/// HTTP round trip.
+fn hook_command(exe: &str, event: &str) -> String {
+    format!("run-shell -b \"'{exe}' _hook fire {event} '#{{session_name}}'\"")
+}
+
+async fn tmux(args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(process::run("tmux", args, None, RunOptions::LOCAL).await?.trim().to_string())
+}
+
+pub async fn run(args: TmuxHookArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
+    match args.command {
+        TmuxHookCommand::Install => {
+            let exe = std::env::current_exe()?.to_string_lossy().to_string();
+            // alert-activity only fires for windows with monitoring on.
+            // Remember the user's setting once, so reinstalling doesn't
+            // record our own "on" as theirs.
+            if tmux(&["show-options", "-gqv", SAVED_MONITOR_ACTIVITY]).await?.is_empty() {
+                let previous = tmux(&["show-options", "-gwv", "monitor-activity"]).await?;
+                tmux(&["set-option", "-g", SAVED_MONITOR_ACTIVITY, &previous]).await?;
+            }
+            tmux(&["set-option", "-gw", "monitor-activity", "on"]).await?;
+            for event in EVENTS {
+                let hook = format!("{event}[{HOOK_INDEX}]");
+                tmux(&["set-hook", "-g", &hook, &hook_command(&exe, event)]).await?;
+            }
+            if human {
+                println!("Registered tmux hooks: {}", EVENTS.join(", "));
+            } else {
+                println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "hooks": EVENTS }))?);
+            }
+        }
+        TmuxHookCommand::Uninstall => {
+            for event in EVENTS {
+                tmux(&["set-hook", "-gu", &format!("{event}[{HOOK_INDEX}]")]).await?;
+            }
+            let previous = tmux(&["show-options", "-gqv", SAVED_MONITOR_ACTIVITY]).await?;
+            if !previous.is_empty() {
+                tmux(&["set-option", "-gw", "monitor-activity", &previous]).await?;
+                tmux(&["set-option", "-gu", SAVED_MONITOR_ACTIVITY]).await?;
+            }
+            if human {
+                println!("Removed tmux hooks: {}", EVENTS.join(", "));
+            } else {
+                println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "hooks": EVENTS }))?);
+            }
+        }
+        TmuxHookCommand::Fire { event, session } => {
+            let Some(session_id) = resolve_session_id(&session) else {
+                return Ok(());
+            };
+            // tmux shows run-shell output in the pane, so stay silent; a
+            // missed event only delays the next activity update.
+            let query = [("sessionId", session_id.as_str()), ("event", event.as_str())];
+            let _ = client.post_empty_with_query("/internal/session-activity", &query).await;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hook_command;
+
+    #[test]
+    fn hook_command_quotes_exe_and_defers_session_name() {
+        assert_eq!(
+            hook_command("/usr/local/bin/rdv", "pane-died"),
+            "run-shell -b \"'/usr/local/bin/rdv' _hook fire pane-died '#{session_name}'\""
+        );
+    }
+}
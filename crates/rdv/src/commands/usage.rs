@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use tabled::{Table, Tabled};
+
+use crate::client::Client;
+use crate::time::format_timestamp;
+
+#[derive(Args)]
+pub struct UsageArgs {
+    /// Only profiles that are currently rate-limited
+    #[arg(long)]
+    limited: bool,
+}
+
+/// A profile's usage-limit windows. Percentages are 0-100 and `None` until
+/// the window has been observed; timestamps are epoch milliseconds.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LimitState {
+    /// available, limited, or unknown.
+    pub limit_status: String,
+    pub window5h_pct: Option<f64>,
+    pub window7d_pct: Option<f64>,
+    pub reset_at5h: Option<i64>,
+    pub reset_at7d: Option<i64>,
+    /// The sooner of the two resets: when a limited account is usable again.
+    pub effective_reset_at: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UsageProfile {
+    pub id: String,
+    pub name: String,
+    /// subscription or api_key.
+    pub account_kind: String,
+    pub email_address: Option<String>,
+    pub limit_state: LimitState,
+    #[serde(default)]
+    pub pools: Vec<String>,
+}
+
+/// `GET /api/claude/usage` response: every Claude-capable agent profile.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct UsageSummary {
+    pub profiles: Vec<UsageProfile>,
+}
+
+impl UsageSummary {
+    fn limited(&self) -> impl Iterator<Item = &UsageProfile> {
+        self.profiles.iter().filter(|p| p.limit_state.limit_status == "limited")
+    }
+
+    /// "3 Claude profiles, 1 limited until ..." style one-liner for `rdv status`.
+    pub(crate) fn describe(&self) -> String {
+        let total = self.profiles.len();
+        let limited: Vec<&UsageProfile> = self.limited().collect();
+        if limited.is_empty() {
+            return format!("{total} Claude profiles, none limited");
+        }
+        let soonest = limited.iter().filter_map(|p| p.limit_state.effective_reset_at).min();
+        match soonest.and_then(format_epoch_ms) {
+            Some(t) => format!("{total} Claude profiles, {} limited (first reset {t})", limited.len()),
+            None => format!("{total} Claude profiles, {} limited", limited.len()),
+        }
+    }
+}
+
+pub(crate) async fn fetch(client: &Client) -> Result<UsageSummary, Box<dyn std::error::Error>> {
+    client.get("/api/claude/usage").await
+}
+
+fn format_epoch_ms(ms: i64) -> Option<String> {
+    DateTime::<Utc>::from_timestamp_millis(ms).map(|t| format_timestamp(&t.to_rfc3339()))
+}
+
+fn format_pct(pct: Option<f64>) -> String {
+    pct.map(|p| format!("{p:.0}%")).unwrap_or_else(|| "-".into())
+}
+
+#[derive(Tabled)]
+struct UsageRow {
+    #[tabled(rename = "Profile")]
+    name: String,
+    #[tabled(rename = "Account")]
+    account: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "5h")]
+    window_5h: String,
+    #[tabled(rename = "7d")]
+    window_7d: String,
+    #[tabled(rename = "Resets")]
+    resets: String,
+}
+
+impl From<&UsageProfile> for UsageRow {
+    fn from(p: &UsageProfile) -> Self {
+        let s = &p.limit_state;
+        Self {
+            name: p.name.clone(),
+            account: p.email_address.clone().unwrap_or_else(|| p.account_kind.clone()),
+            status: s.limit_status.clone(),
+            window_5h: format_pct(s.window5h_pct),
+            window_7d: format_pct(s.window7d_pct),
+            resets: s.effective_reset_at.and_then(format_epoch_ms).unwrap_or_else(|| "-".into()),
+        }
+    }
+}
+
+pub async fn run(args: UsageArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut usage = fetch(client).await?;
+    if args.limited {
+        usage.profiles.retain(|p| p.limit_state.limit_status == "limited");
+    }
+    if human {
+        if usage.profiles.is_empty() {
+            println!("No Claude profiles{}.", if args.limited { " are limited" } else { "" });
+        } else {
+            let rows: Vec<UsageRow> = usage.profiles.iter().map(UsageRow::from).collect();
+            println!("{}", Table::new(rows));
+        }
+    } else {
+        println!("{}", serde_json::to_string_pretty(&usage)?);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UsageSummary;
+
+    fn summary(statuses: &[(&str, Option<i64>)]) -> UsageSummary {
+        let profiles: Vec<serde_json::Value> = statuses
+            .iter()
+            .enumerate()
+            .map(|(i, (status, reset))| {
+                serde_json::json!({
+                    "id": format!("p-{i}"),
+                    "name": format!("profile {i}"),
+                    "accountKind": "subscription",
+                    "emailAddress": null,
+                    "organizationName": null,
+                    "limitState": {
+                        "limitStatus": status,
+                        "window5hPct": 100,
+                        "window7dPct": null,
+                        "resetAt5h": reset,
+                        "resetAt7d": null,
+                        "effectiveResetAt": reset,
+                    },
+                    "pools": [],
+                })
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({ "profiles": profiles })).unwrap()
+    }
+
+    #[test]
+    fn describe_counts_limited_profiles() {
+        assert_eq!(
+            summary(&[("available", None), ("unknown", None)]).describe(),
+            "2 Claude profiles, none limited"
+        );
+        assert_eq!(
+            summary(&[("available", None), ("limited", None)]).describe(),
+            "2 Claude profiles, 1 limited"
+        );
+        assert!(summary(&[("limited", Some(1_767_225_600_000))])
+            .describe()
+            .starts_with("1 Claude profiles, 1 limited (first reset "));
+    }
+}
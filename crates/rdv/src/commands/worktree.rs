@@ -1,8 +1,11 @@
+use std::path::Path;
+
 use clap::{Args, Subcommand};
 use serde_json::json;
 use tabled::{Table, Tabled};
 
 use crate::client::Client;
+use crate::preflight;
 
 #[derive(Args)]
 pub struct WorktreeArgs {
@@ -20,6 +23,12 @@ enum WorktreeCommand {
         /// Branch name for the worktree
         #[arg(long)]
         branch: String,
+        /// Reuse an existing worktree if it is clean and on the requested branch
+        #[arg(long)]
+        reuse_existing: bool,
+        /// Skip the local git, LFS and disk space checks
+        #[arg(long)]
+        skip_preflight: bool,
     },
     /// List worktrees for a repository
     List {
@@ -56,15 +65,83 @@ struct WorktreeRow {
     path: String,
 }
 
+/// Resolve the worktree's owning session: explicit argument, else
+/// `RDV_SESSION_ID`. Validated as a UUID since it is interpolated into a path.
+pub(crate) fn resolve_session_id(
+    id: Option<String>,
+    client: &Client,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let session_id = match id {
+        Some(id) => id,
+        None => client
+            .session_id()
+            .ok_or("no session ID given and RDV_SESSION_ID is not set")?
+            .to_string(),
+    };
+    if !is_session_id(&session_id) {
+        return Err(format!("'{session_id}' is not a valid session ID").into());
+    }
+    Ok(session_id)
+}
+
+fn is_session_id(id: &str) -> bool {
+    id.len() == 36 && id.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+/// Human summary of a create response. Under parallel session creation the
+/// server may suffix the branch (`feat-x-2`) to dodge a collision, or hand back
+/// an existing worktree in `--reuse-existing` mode; both are called out so the
+/// caller doesn't keep using the name it asked for.
+fn describe_created(requested: &str, result: &serde_json::Value) -> String {
+    let branch = result["branch"].as_str().unwrap_or(requested);
+    let path = result["worktreePath"].as_str().unwrap_or("?");
+    let verb = if result["reused"].as_bool() == Some(true) {
+        "Reused"
+    } else {
+        "Created"
+    };
+    let mut line = format!("{verb} worktree {path} on {branch}");
+    if branch != requested {
+        line.push_str(&format!(" (requested {requested} was taken)"));
+    }
+    line
+}
+
 pub async fn run(args: WorktreeArgs, client: &Client, human: bool) -> Result<(), Box<dyn std::error::Error>> {
     match args.command {
-        WorktreeCommand::Create { repo, branch } => {
+        WorktreeCommand::Create {
+            repo,
+            branch,
+            reuse_existing,
+            skip_preflight,
+        } => {
+            if !skip_preflight {
+                let failed: Vec<String> = preflight::for_worktree(Path::new(&repo))
+                    .await
+                    .into_iter()
+                    .filter(|c| !c.ok)
+                    .map(|c| format!("{}: {}", c.name, c.detail.unwrap_or_default()))
+                    .collect();
+                if !failed.is_empty() {
+                    return Err(format!(
+                        "pre-flight checks failed (use --skip-preflight to override):\n  {}",
+                        failed.join("\n  ")
+                    )
+                    .into());
+                }
+            }
             let body = json!({
-                "repoPath": repo,
+                "projectPath": repo,
                 "branch": branch,
+                "createNewBranch": true,
+                "reuseExisting": reuse_existing,
             });
             let result: serde_json::Value = client.post_json("/api/github/worktrees", &body).await?;
-            println!("{}", serde_json::to_string_pretty(&result)?);
+            if human {
+                println!("{}", describe_created(&branch, &result));
+            } else {
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            }
         }
         WorktreeCommand::List { repo } => {
             let body = json!({ "repoPath": repo });
@@ -99,9 +176,7 @@ pub async fn run(args: WorktreeArgs, client: &Client, human: bool) -> Result<(),
             let session_id = client.session_id()
                 .ok_or("RDV_SESSION_ID is not set. This command must be run from within an agent session.")?;
             // Validate session ID format (UUID) to prevent path injection
-            if session_id.len() != 36
-                || !session_id.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
-            {
+            if !is_session_id(session_id) {
                 return Err("RDV_SESSION_ID is not a valid session ID".into());
             }
             let path = format!(
@@ -114,3 +189,32 @@ pub async fn run(args: WorktreeArgs, client: &Client, human: bool) -> Result<(),
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{describe_created, is_session_id};
+
+    #[test]
+    fn describe_created_flags_disambiguated_branch() {
+        let same = json!({ "branch": "feat-x", "worktreePath": "/w/feat-x" });
+        assert_eq!(describe_created("feat-x", &same), "Created worktree /w/feat-x on feat-x");
+
+        let suffixed = json!({ "branch": "feat-x-2", "worktreePath": "/w/feat-x-2" });
+        assert_eq!(
+            describe_created("feat-x", &suffixed),
+            "Created worktree /w/feat-x-2 on feat-x-2 (requested feat-x was taken)"
+        );
+
+        let reused = json!({ "branch": "feat-x", "worktreePath": "/w/feat-x", "reused": true });
+        assert_eq!(describe_created("feat-x", &reused), "Reused worktree /w/feat-x on feat-x");
+    }
+
+    #[test]
+    fn session_id_must_look_like_a_uuid() {
+        assert!(is_session_id("550e8400-e29b-41d4-a716-446655440000"));
+        assert!(!is_session_id("../../etc/passwd"));
+        assert!(!is_session_id("550e8400"));
+    }
+}
@@ -1,5 +1,10 @@
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::time::DisplayZone;
 
 /// How the CLI connects to a server.
 #[derive(Debug, Clone)]
@@ -24,25 +29,30 @@ pub struct ServerConfig {
     pub session_id: Option<String>,
     /// Bearer token for API authentication.
     pub api_key: Option<String>,
+    /// Journal deliverable-later calls when the server is down
+    /// (`RDV_OFFLINE_QUEUE=1`).
+    pub offline_queue: bool,
+    /// Print each request's status and latency to stderr (`RDV_TRACE=1`).
+    pub trace: bool,
 }
 
 impl ServerConfig {
-    /// Build config from environment variables.
+    /// Build config from environment variables, then the settings file.
     ///
     /// API server priority:
     /// 1. `RDV_API_SOCKET` -> Unix socket (explicit)
     /// 2. `RDV_API_PORT` -> TCP localhost:<port>
-    /// 3. Auto-detect `~/.remote-dev/run/nextjs.sock`
-    /// 4. Fallback TCP localhost:6001
+    /// 3. `api_socket`, then `api_port`, from the settings file
+    /// 4. Auto-detect `~/.remote-dev/run/nextjs.sock`
+    /// 5. Fallback TCP localhost:6001
     ///
-    /// Terminal server priority:
-    /// 1. `RDV_TERMINAL_SOCKET` -> Unix socket (explicit)
-    /// 2. `RDV_TERMINAL_PORT` -> TCP localhost:<port>
-    /// 3. Auto-detect `~/.remote-dev/run/terminal.sock`
-    /// 4. Fallback TCP localhost:6002
+    /// Terminal server priority is the same with `RDV_TERMINAL_*`,
+    /// `terminal_*` and `~/.remote-dev/run/terminal.sock`, falling back to
+    /// TCP localhost:6002.
     pub fn from_env() -> Self {
         let session_id = env::var("RDV_SESSION_ID").ok();
         let base_dir = dirs_fallback();
+        let file = file_settings();
 
         // API key: env var takes precedence, then file-based local key.
         // The local key file (~/.remote-dev/rdv/.local-key) is written by the
@@ -61,6 +71,7 @@ impl ServerConfig {
         let api = resolve_connection(
             "RDV_API_SOCKET",
             "RDV_API_PORT",
+            (file.api_socket.as_deref(), file.api_port),
             base_dir.join("run/nextjs.sock"),
             6001,
         );
@@ -68,15 +79,21 @@ impl ServerConfig {
         let terminal = resolve_connection(
             "RDV_TERMINAL_SOCKET",
             "RDV_TERMINAL_PORT",
+            (file.terminal_socket.as_deref(), file.terminal_port),
             base_dir.join("run/terminal.sock"),
             6002,
         );
 
+        let offline_queue = env_flag("RDV_OFFLINE_QUEUE").or(file.offline_queue).unwrap_or(false);
+        let trace = env_flag("RDV_TRACE").or(file.trace).unwrap_or(false);
+
         Self {
             api,
             terminal,
             session_id,
             api_key,
+            offline_queue,
+            trace,
         }
     }
 
@@ -91,17 +108,48 @@ impl ServerConfig {
     }
 }
 
-/// Resolve a connection method from env vars with auto-detect fallback.
+/// `Some(on)` when the variable is set, whatever its value.
+fn env_flag(name: &str) -> Option<bool> {
+    env::var(name).ok().map(|v| matches!(v.as_str(), "1" | "true" | "yes"))
+}
+
+/// A TCP port; 0 would ask the OS for any free one, which is never a server.
+pub fn parse_port(value: &str) -> Option<u16> {
+    value.parse::<u16>().ok().filter(|&p| p != 0)
+}
+
+/// The environment's value for `s` when rdv actually uses it. A port that
+/// doesn't parse, or a zone it doesn't know, is passed over for the file
+/// and default just as when resolving the connection.
+pub fn env_override(s: &Setting) -> Option<String> {
+    let value = env::var(s.env).ok()?;
+    match s.kind {
+        Kind::Path => Some(value),
+        Kind::Port => parse_port(&value).map(|p| p.to_string()),
+        Kind::Bool => env_flag(s.env).map(|on| on.to_string()),
+        Kind::Zone => DisplayZone::parse(&value).map(|_| value),
+    }
+}
+
+/// Resolve a connection method from env vars, then the settings file's
+/// `(socket, port)`, with auto-detect fallback.
 fn resolve_connection(
     socket_env: &str,
     port_env: &str,
+    file: (Option<&Path>, Option<u16>),
     default_socket: PathBuf,
     default_port: u16,
 ) -> ConnectionMethod {
     if let Ok(sock) = env::var(socket_env) {
         return ConnectionMethod::UnixSocket(PathBuf::from(sock));
     }
-    if let Some(port) = env::var(port_env).ok().and_then(|p| p.parse::<u16>().ok()) {
+    if let Some(port) = env::var(port_env).ok().and_then(|p| parse_port(&p)) {
+        return ConnectionMethod::Tcp(format!("localhost:{port}"));
+    }
+    if let Some(sock) = file.0 {
+        return ConnectionMethod::UnixSocket(sock.to_path_buf());
+    }
+    if let Some(port) = file.1 {
         return ConnectionMethod::Tcp(format!("localhost:{port}"));
     }
     if default_socket.exists() {
@@ -121,6 +169,13 @@ fn base_url_for(method: &ConnectionMethod) -> String {
     }
 }
 
+/// Local data directory: `RDV_DATA_DIR` if set, else `~/.remote-dev`.
+pub fn data_dir() -> PathBuf {
+    env::var("RDV_DATA_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| dirs_fallback())
+}
+
 /// Return `~/.remote-dev` without pulling in the `dirs` crate.
 fn dirs_fallback() -> PathBuf {
     env::var("HOME")
@@ -128,3 +183,130 @@ fn dirs_fallback() -> PathBuf {
         .unwrap_or_else(|_| PathBuf::from("/tmp"))
         .join(".remote-dev")
 }
+
+/// Type of a settings-file value, for validating `rdv config set`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Path,
+    Port,
+    Bool,
+    Zone,
+}
+
+/// One key the settings file accepts.
+pub struct Setting {
+    pub key: &'static str,
+    /// Environment variable that overrides the file.
+    pub env: &'static str,
+    pub kind: Kind,
+    /// What applies when neither the file nor the environment sets it.
+    pub default: &'static str,
+}
+
+pub const SETTINGS: [Setting; 7] = [
+    Setting { key: "api_socket", env: "RDV_API_SOCKET", kind: Kind::Path, default: "~/.remote-dev/run/nextjs.sock if present" },
+    Setting { key: "api_port", env: "RDV_API_PORT", kind: Kind::Port, default: "6001" },
+    Setting { key: "terminal_socket", env: "RDV_TERMINAL_SOCKET", kind: Kind::Path, default: "~/.remote-dev/run/terminal.sock if present" },
+    Setting { key: "terminal_port", env: "RDV_TERMINAL_PORT", kind: Kind::Port, default: "6002" },
+    Setting { key: "offline_queue", env: "RDV_OFFLINE_QUEUE", kind: Kind::Bool, default: "false" },
+    Setting { key: "trace", env: "RDV_TRACE", kind: Kind::Bool, default: "false" },
+    Setting { key: "timezone", env: "RDV_TIMEZONE", kind: Kind::Zone, default: "local" },
+];
+
+pub fn setting(key: &str) -> Option<&'static Setting> {
+    SETTINGS.iter().find(|s| s.key == key)
+}
+
+/// Contents of the settings file. Every key is optional; see `SETTINGS`.
+#[derive(Debug, Default, Deserialize)]
+pub struct FileSettings {
+    pub api_socket: Option<PathBuf>,
+    pub api_port: Option<u16>,
+    pub terminal_socket: Option<PathBuf>,
+    pub terminal_port: Option<u16>,
+    pub offline_queue: Option<bool>,
+    pub trace: Option<bool>,
+    pub timezone: Option<String>,
+}
+
+/// Settings file: `RDV_CONFIG` if set, else `~/.remote-dev/rdv/config.toml`.
+pub fn settings_path() -> PathBuf {
+    env::var("RDV_CONFIG")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| dirs_fallback().join("rdv/config.toml"))
+}
+
+/// Parse settings-file text. Unknown keys are returned, not rejected, so a
+/// file written for a newer rdv still loads.
+pub fn parse_settings(text: &str) -> Result<(FileSettings, Vec<String>), String> {
+    let mut table: toml::Table = toml::from_str(text).map_err(|e| e.message().to_string())?;
+    let unknown: Vec<String> = table.keys().filter(|k| setting(k).is_none()).cloned().collect();
+    for key in &unknown {
+        table.remove(key);
+    }
+    let settings = FileSettings::deserialize(toml::Value::Table(table)).map_err(|e| e.message().to_string())?;
+    for (key, port) in [("api_port", settings.api_port), ("terminal_port", settings.terminal_port)] {
+        if port == Some(0) {
+            return Err(format!("{key} must be a port number (1-65535)"));
+        }
+    }
+    if let Some(ref tz) = settings.timezone {
+        if DisplayZone::parse(tz).is_none() {
+            return Err(format!("timezone {tz:?} is not local, UTC or an IANA zone name"));
+        }
+    }
+    Ok((settings, unknown))
+}
+
+/// Unknown keys in the settings file, or why the whole file is ignored. A
+/// missing file has neither. Only `rdv config` and `rdv doctor` report
+/// these, so a key for a newer rdv doesn't clutter every command's stderr.
+pub fn settings_file_report() -> Result<Vec<String>, String> {
+    match std::fs::read_to_string(settings_path()) {
+        Ok(text) => parse_settings(&text).map(|(_, unknown)| unknown),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// The settings file, read once per process. A missing file is empty; a
+/// broken one is reported on stderr and ignored so every command still runs.
+/// Unknown keys are skipped quietly; see `settings_file_report`.
+pub fn file_settings() -> &'static FileSettings {
+    static SETTINGS_FILE: OnceLock<FileSettings> = OnceLock::new();
+    SETTINGS_FILE.get_or_init(|| {
+        let path = settings_path();
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return FileSettings::default();
+        };
+        match parse_settings(&text) {
+            Ok((settings, _)) => settings,
+            Err(e) => {
+                eprintln!("rdv: ignoring {}: {e}", path.display());
+                FileSettings::default()
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_settings;
+
+    #[test]
+    fn settings_parse_and_report_unknown_keys() {
+        let (s, unknown) = parse_settings("api_port = 7001\ntrace = true\ncolour = \"auto\"\n").unwrap();
+        assert_eq!(s.api_port, Some(7001));
+        assert_eq!(s.trace, Some(true));
+        assert_eq!(unknown, ["colour"]);
+    }
+
+    #[test]
+    fn settings_reject_wrong_types_and_zones() {
+        assert!(parse_settings("api_port = \"high\"\n").is_err());
+        assert!(parse_settings("api_port = 70000\n").is_err());
+        assert!(parse_settings("terminal_port = 0\n").is_err());
+        assert!(parse_settings("timezone = \"Mars/Olympus\"\n").is_err());
+        assert!(parse_settings("timezone = \"Europe/Berlin\"\n").is_ok());
+    }
+}
@@ -0,0 +1,330 @@
+//! `rdv monitor`: a live dashboard of sessions, with keys to act on
+//! the selected row.
+//!
+//! Data is re-fetched every few seconds. A failed refresh keeps the last
+//! good data on screen and shows the error in the footer.
+
+use std::time::{Duration, Instant};
+
+use chrono::Local;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::client::Client;
+
+const REFRESH_EVERY: Duration = Duration::from_secs(3);
+const INPUT_POLL: Duration = Duration::from_millis(200);
+
+type BoxError = Box<dyn std::error::Error>;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionView {
+    id: String,
+    name: Option<String>,
+    status: Option<String>,
+    agent_provider: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SessionsResponse {
+    sessions: Vec<SessionView>,
+}
+
+/// What the footer is doing: showing help, collecting nudge text, or
+/// waiting for a kill to be confirmed.
+#[derive(Debug, PartialEq)]
+enum Mode {
+    Normal,
+    Nudge { session_id: String, text: String },
+    ConfirmKill { session_id: String },
+}
+
+/// A request the event loop must make on the user's behalf.
+#[derive(Debug, PartialEq)]
+enum Action {
+    Quit,
+    Peek(String),
+    Nudge(String, String),
+    Kill(String),
+}
+
+struct App {
+    sessions: Vec<SessionView>,
+    session_state: TableState,
+    mode: Mode,
+    /// Screen capture of the peeked session, shown under the table.
+    peek: Option<(String, String)>,
+    /// Last error or confirmation, replacing the key help until the next key.
+    message: Option<String>,
+    refreshed_at: Option<String>,
+}
+
+impl App {
+    fn new() -> Self {
+        Self {
+            sessions: Vec::new(),
+            session_state: TableState::default().with_selected(Some(0)),
+            mode: Mode::Normal,
+            peek: None,
+            message: None,
+            refreshed_at: None,
+        }
+    }
+
+    fn selected_session(&self) -> Option<&SessionView> {
+        self.session_state.selected().and_then(|i| self.sessions.get(i))
+    }
+
+    /// Keep the selection on a row after the list shrinks.
+    fn clamp_selection(&mut self) {
+        let i = self.session_state.selected().unwrap_or(0);
+        self.session_state.select(Some(i.min(self.sessions.len().saturating_sub(1))));
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        let last = self.sessions.len().saturating_sub(1);
+        let i = self.session_state.selected().unwrap_or(0);
+        let next = if down { (i + 1).min(last) } else { i.saturating_sub(1) };
+        self.session_state.select(Some(next));
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        self.message = None;
+        match std::mem::replace(&mut self.mode, Mode::Normal) {
+            Mode::Nudge { session_id, mut text } => {
+                match key.code {
+                    KeyCode::Enter if !text.is_empty() => return Some(Action::Nudge(session_id, text)),
+                    KeyCode::Esc => {}
+                    KeyCode::Backspace => {
+                        text.pop();
+                        self.mode = Mode::Nudge { session_id, text };
+                    }
+                    KeyCode::Char(c) => {
+                        text.push(c);
+                        self.mode = Mode::Nudge { session_id, text };
+                    }
+                    _ => self.mode = Mode::Nudge { session_id, text },
+                }
+                None
+            }
+            Mode::ConfirmKill { session_id } => match key.code {
+                KeyCode::Char('y') => Some(Action::Kill(session_id)),
+                _ => None,
+            },
+            Mode::Normal => match key.code {
+                KeyCode::Char('q') => Some(Action::Quit),
+                KeyCode::Esc if self.peek.is_some() => {
+                    self.peek = None;
+                    None
+                }
+                KeyCode::Esc => Some(Action::Quit),
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.move_selection(true);
+                    None
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.move_selection(false);
+                    None
+                }
+                KeyCode::Char('p') => self.selected_session().map(|s| Action::Peek(s.id.clone())),
+                KeyCode::Char('n') => {
+                    if let Some(s) = self.selected_session() {
+                        self.mode = Mode::Nudge { session_id: s.id.clone(), text: String::new() };
+                    }
+                    None
+                }
+                KeyCode::Char('x') => {
+                    if let Some(s) = self.selected_session() {
+                        self.mode = Mode::ConfirmKill { session_id: s.id.clone() };
+                    }
+                    None
+                }
+                _ => None,
+            },
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame) {
+        let peek_height = if self.peek.is_some() { Constraint::Percentage(40) } else { Constraint::Length(0) };
+        let [header, sessions, peek, footer] =
+            Layout::vertical([Constraint::Length(1), Constraint::Min(6), peek_height, Constraint::Length(1)])
+                .areas(frame.area());
+
+        let title = format!(
+            " rdv monitor  {} sessions{}",
+            self.sessions.len(),
+            self.refreshed_at.as_deref().map(|t| format!("  (updated {t})")).unwrap_or_default()
+        );
+        frame.render_widget(Line::from(title).bold(), header);
+
+        let session_rows: Vec<Row> = self
+            .sessions
+            .iter()
+            .map(|s| {
+                Row::new(vec![
+                    s.name.clone().unwrap_or_else(|| s.id.clone()),
+                    s.status.clone().unwrap_or_default(),
+                    s.agent_provider.clone().unwrap_or_default(),
+                ])
+            })
+            .collect();
+        let table = Table::new(session_rows, [Constraint::Fill(2), Constraint::Length(10), Constraint::Length(9)])
+            .header(Row::new(["Name", "Status", "Agent"]).bold())
+            .block(Block::bordered().title(" Sessions ").border_style(Style::new().fg(Color::Cyan)))
+            .row_highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(table, sessions, &mut self.session_state);
+
+        if let Some((session_id, content)) = &self.peek {
+            // Show the bottom of the capture, where the agent is writing.
+            let visible = peek.height.saturating_sub(2) as usize;
+            let lines: Vec<&str> = content.lines().collect();
+            let tail = lines[lines.len().saturating_sub(visible)..].join("\n");
+            let block = Block::bordered().title(format!(" Peek {session_id} (Esc to close) "));
+            frame.render_widget(Paragraph::new(tail).block(block), peek);
+        }
+
+        let footer_text = match (&self.mode, &self.message) {
+            (Mode::Nudge { text, .. }, _) => format!("Nudge: {text}_  (Enter send, Esc cancel)"),
+            (Mode::ConfirmKill { session_id }, _) => format!("Kill session {session_id}? (y to confirm)"),
+            (Mode::Normal, Some(m)) => m.clone(),
+            (Mode::Normal, None) => "q quit  j/k move  p peek  n nudge  x kill".into(),
+        };
+        frame.render_widget(Line::from(footer_text).dim(), footer);
+    }
+}
+
+async fn refresh(app: &mut App, client: &Client) -> Result<(), BoxError> {
+    let sessions: SessionsResponse = client.get("/api/sessions").await?;
+    app.sessions = sessions.sessions;
+    app.clamp_selection();
+    app.refreshed_at = Some(Local::now().format("%H:%M:%S").to_string());
+    Ok(())
+}
+
+/// Perform `action`; returns the footer message to show, `None` to quit.
+async fn perform(app: &mut App, client: &Client, action: Action) -> Option<Result<String, BoxError>> {
+    let result = match action {
+        Action::Quit => return None,
+        Action::Peek(id) => {
+            let query = [("sessionId", id.as_str())];
+            let screen: Result<serde_json::Value, _> = client.get_with_query("/internal/screen", &query).await;
+            screen.map(|s| {
+                let content = s.get("content").and_then(|c| c.as_str()).unwrap_or("").to_string();
+                app.peek = Some((id, content));
+                String::new()
+            })
+        }
+        Action::Nudge(id, text) => async {
+            client.post_json("/internal/pty-write", &json!({ "sessionId": id, "text": text })).await?;
+            client.post_json("/internal/pty-key", &json!({ "sessionId": id, "key": "Enter" })).await?;
+            Ok(format!("Nudged {id}"))
+        }
+        .await,
+        Action::Kill(id) => client.delete(&format!("/api/sessions/{id}")).await.map(|_| format!("Closed {id}")),
+    };
+    Some(result)
+}
+
+async fn event_loop(terminal: &mut DefaultTerminal, client: &Client) -> Result<(), BoxError> {
+    let mut app = App::new();
+    let mut last_refresh: Option<Instant> = None;
+    loop {
+        if last_refresh.is_none_or(|t| t.elapsed() >= REFRESH_EVERY) {
+            if let Err(e) = refresh(&mut app, client).await {
+                app.message = Some(format!("refresh failed: {e}"));
+            }
+            last_refresh = Some(Instant::now());
+        }
+        terminal.draw(|f| app.render(f))?;
+
+        let input = tokio::task::block_in_place(|| -> std::io::Result<Option<Event>> {
+            if event::poll(INPUT_POLL)? {
+                event::read().map(Some)
+            } else {
+                Ok(None)
+            }
+        })?;
+        let Some(Event::Key(key)) = input else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let Some(action) = app.handle_key(key) else { continue };
+        let refetch = matches!(action, Action::Kill(_));
+        match perform(&mut app, client, action).await {
+            None => return Ok(()),
+            Some(Ok(msg)) => app.message = Some(msg).filter(|m| !m.is_empty()),
+            Some(Err(e)) => app.message = Some(format!("error: {e}")),
+        }
+        if refetch {
+            last_refresh = None;
+        }
+    }
+}
+
+pub async fn run(client: &Client) -> Result<(), BoxError> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, client).await;
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+    use ratatui::Terminal;
+
+    use super::{Action, App, Mode, SessionView};
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.sessions = ["s-1", "s-2"]
+            .map(|id| SessionView {
+                id: id.into(),
+                name: Some(format!("{id}-name")),
+                status: Some("active".into()),
+                agent_provider: Some("claude".into()),
+            })
+            .into();
+        app
+    }
+
+    fn press(app: &mut App, code: KeyCode) -> Option<Action> {
+        app.handle_key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn sessions_are_listed() {
+        let mut app = app();
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|f| app.render(f)).unwrap();
+        let screen: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(screen.contains("2 sessions"));
+        assert!(screen.contains("s-2-name"));
+    }
+
+    #[test]
+    fn kill_needs_confirmation_and_nudge_collects_text() {
+        let mut app = app();
+        press(&mut app, KeyCode::Down);
+        assert_eq!(press(&mut app, KeyCode::Char('x')), None);
+        assert_eq!(press(&mut app, KeyCode::Char('y')), Some(Action::Kill("s-2".into())));
+
+        assert_eq!(press(&mut app, KeyCode::Char('x')), None);
+        assert_eq!(press(&mut app, KeyCode::Char('n')), None);
+        assert_eq!(app.mode, Mode::Normal);
+
+        press(&mut app, KeyCode::Char('n'));
+        for c in "go on".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        assert_eq!(press(&mut app, KeyCode::Enter), Some(Action::Nudge("s-2".into(), "go on".into())));
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::json;
+
+/// A failed API call, classified so callers can decide whether to retry.
+///
+/// Commands still return `Box<dyn Error>`; code that cares downcasts with
+/// [`ApiError::from_boxed`]. `Display` keeps the `HTTP {code} {reason}: {body}`
+/// shape users and scripts already match on.
+#[derive(Debug)]
+pub enum ApiError {
+    /// The server could not be reached (socket missing, refused, timed out).
+    ConnectionFailed(String),
+    /// Any other transport failure while sending or reading the response.
+    Transport(String),
+    /// 401 or 403: missing or rejected API key.
+    Unauthorized(HttpFailure),
+    /// 404.
+    NotFound(HttpFailure),
+    /// 409: the resource changed underneath the request.
+    Conflict(HttpFailure),
+    /// A folder's session limit is reached (`code: "QUOTA_EXCEEDED"`,
+    /// whatever the status).
+    QuotaExceeded(HttpFailure),
+    /// 429 that outlasted the client's own retries.
+    RateLimited {
+        failure: HttpFailure,
+        retry_after: Option<Duration>,
+    },
+    /// 5xx.
+    ServerError(HttpFailure),
+    /// Any other 4xx.
+    Rejected(HttpFailure),
+}
+
+/// The parts of an HTTP error response worth keeping.
+#[derive(Debug)]
+pub struct HttpFailure {
+    pub status: u16,
+    pub reason: &'static str,
+    /// Raw response body, shown to the user.
+    pub body: String,
+    /// `detail` (or `error`) from a problem-details JSON body.
+    pub detail: Option<String>,
+    /// Server-assigned request ID, from the body or `x-request-id`.
+    pub request_id: Option<String>,
+    /// Machine-readable error code from the body, e.g. `QUOTA_EXCEEDED`.
+    pub code: Option<String>,
+}
+
+/// Problem-details body (RFC 9457), plus the `error` key older routes use.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Problem {
+    detail: Option<String>,
+    error: Option<String>,
+    request_id: Option<String>,
+    code: Option<String>,
+}
+
+impl HttpFailure {
+    pub fn new(status: u16, reason: &'static str, body: String, header_request_id: Option<String>) -> Self {
+        let problem = serde_json::from_str::<Problem>(&body).ok();
+        let (detail, body_request_id, code) = match problem {
+            Some(p) => (p.detail.or(p.error), p.request_id, p.code),
+            None => (None, None, None),
+        };
+        Self {
+            status,
+            reason,
+            body,
+            detail,
+            request_id: body_request_id.or(header_request_id),
+            code,
+        }
+    }
+}
+
+impl ApiError {
+    /// Classify an HTTP error response.
+    pub fn from_status(failure: HttpFailure, retry_after: Option<Duration>) -> Self {
+        if failure.code.as_deref() == Some("QUOTA_EXCEEDED") {
+            return ApiError::QuotaExceeded(failure);
+        }
+        match failure.status {
+            401 | 403 => ApiError::Unauthorized(failure),
+            404 => ApiError::NotFound(failure),
+            409 => ApiError::Conflict(failure),
+            429 => ApiError::RateLimited { failure, retry_after },
+            500..=599 => ApiError::ServerError(failure),
+            _ => ApiError::Rejected(failure),
+        }
+    }
+
+    pub fn from_reqwest(err: reqwest::Error) -> Self {
+        if err.is_connect() || err.is_timeout() {
+            ApiError::ConnectionFailed(err.to_string())
+        } else {
+            ApiError::Transport(err.to_string())
+        }
+    }
+
+    /// Find an `ApiError` inside a command's boxed error.
+    pub fn from_boxed<'a>(err: &'a (dyn std::error::Error + 'static)) -> Option<&'a ApiError> {
+        err.downcast_ref::<ApiError>()
+    }
+
+    pub fn http(&self) -> Option<&HttpFailure> {
+        match self {
+            ApiError::Unauthorized(f)
+            | ApiError::NotFound(f)
+            | ApiError::Conflict(f)
+            | ApiError::QuotaExceeded(f)
+            | ApiError::ServerError(f)
+            | ApiError::Rejected(f)
+            | ApiError::RateLimited { failure: f, .. } => Some(f),
+            ApiError::ConnectionFailed(_) | ApiError::Transport(_) => None,
+        }
+    }
+
+    /// The server's one-line explanation if it sent one, else the full
+    /// error text.
+    pub fn summary(&self) -> String {
+        match self.http().and_then(|f| f.detail.as_deref()) {
+            Some(detail) => format!("HTTP {}: {detail}", self.http().map_or(0, |f| f.status)),
+            None => self.to_string(),
+        }
+    }
+
+    /// Stable machine-readable name for the failure class.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ApiError::ConnectionFailed(_) => "connection_failed",
+            ApiError::Transport(_) => "transport",
+            ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::NotFound(_) => "not_found",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::QuotaExceeded(_) => "quota_exceeded",
+            ApiError::RateLimited { .. } => "rate_limited",
+            ApiError::ServerError(_) => "server_error",
+            ApiError::Rejected(_) => "rejected",
+        }
+    }
+
+    /// Whether the same request may succeed if sent again later. A transport
+    /// failure (say, a response body cut off mid-read) counts, but isn't
+    /// "unreachable": the server may already have acted on the request.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ApiError::ConnectionFailed(_)
+                | ApiError::Transport(_)
+                | ApiError::RateLimited { .. }
+                | ApiError::ServerError(_)
+        )
+    }
+
+    /// How long to wait before retrying, when the server said.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ApiError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::ConnectionFailed(e) => write!(f, "cannot reach server: {e}"),
+            ApiError::Transport(e) => f.write_str(e),
+            _ => {
+                let h = self.http().expect("HTTP variants carry a failure");
+                write!(f, "HTTP {} {}", h.status, h.reason)?;
+                if !h.body.is_empty() {
+                    write!(f, ": {}", h.body)?;
+                }
+                if let Some(ref id) = h.request_id {
+                    write!(f, " (request {id})")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// A command's error as the `--json` object written to stderr. Failures
+/// that never reached the server (bad flags, local I/O) get kind `error`.
+pub fn error_json(err: &(dyn std::error::Error + 'static)) -> serde_json::Value {
+    let Some(api) = ApiError::from_boxed(err) else {
+        return json!({ "error": { "kind": "error", "message": err.to_string() } });
+    };
+    let http = api.http();
+    json!({
+        "error": {
+            "kind": api.kind(),
+            "message": api.summary(),
+            "status": http.map(|h| h.status),
+            "detail": http.and_then(|h| h.detail.as_deref()),
+            "requestId": http.and_then(|h| h.request_id.as_deref()),
+            "retryable": api.is_retryable(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_json, ApiError, HttpFailure};
+
+    fn failure(status: u16, body: &str) -> HttpFailure {
+        HttpFailure::new(status, "Reason", body.into(), None)
+    }
+
+    #[test]
+    fn classifies_by_status() {
+        assert!(matches!(ApiError::from_status(failure(401, ""), None), ApiError::Unauthorized(_)));
+        assert!(matches!(ApiError::from_status(failure(409, ""), None), ApiError::Conflict(_)));
+        assert!(matches!(ApiError::from_status(failure(503, ""), None), ApiError::ServerError(_)));
+        assert!(matches!(ApiError::from_status(failure(422, ""), None), ApiError::Rejected(_)));
+        assert!(matches!(
+            ApiError::from_status(failure(409, r#"{"code":"QUOTA_EXCEEDED"}"#), None),
+            ApiError::QuotaExceeded(_)
+        ));
+    }
+
+    #[test]
+    fn only_transient_failures_are_retryable() {
+        assert!(ApiError::ConnectionFailed("refused".into()).is_retryable());
+        assert!(ApiError::Transport("error decoding response body".into()).is_retryable());
+        assert!(ApiError::from_status(failure(502, ""), None).is_retryable());
+        assert!(ApiError::from_status(failure(429, ""), None).is_retryable());
+        assert!(!ApiError::from_status(failure(404, ""), None).is_retryable());
+        assert!(!ApiError::from_status(failure(409, ""), None).is_retryable());
+    }
+
+    #[test]
+    fn reads_problem_details_and_keeps_display_shape() {
+        let err = ApiError::from_status(
+            failure(409, r#"{"title":"Conflict","detail":"branch exists","requestId":"req-7"}"#),
+            None,
+        );
+        let http = err.http().unwrap();
+        assert_eq!(http.detail.as_deref(), Some("branch exists"));
+        assert_eq!(http.request_id.as_deref(), Some("req-7"));
+        assert!(err.to_string().starts_with("HTTP 409 Reason: {"));
+        assert!(err.to_string().ends_with("(request req-7)"));
+    }
+
+    #[test]
+    fn legacy_error_key_becomes_detail() {
+        let f = failure(404, r#"{"error":"Session not found"}"#);
+        assert_eq!(f.detail.as_deref(), Some("Session not found"));
+        assert_eq!(f.request_id, None);
+        assert_eq!(ApiError::from_status(f, None).summary(), "HTTP 404: Session not found");
+    }
+
+    #[test]
+    fn json_errors_carry_kind_and_request_id() {
+        let err: Box<dyn std::error::Error> = Box::new(ApiError::from_status(
+            failure(404, r#"{"detail":"no such session","requestId":"req-9"}"#),
+            None,
+        ));
+        let v = error_json(err.as_ref());
+        assert_eq!(v["error"]["kind"], "not_found");
+        assert_eq!(v["error"]["status"], 404);
+        assert_eq!(v["error"]["requestId"], "req-9");
+        assert_eq!(v["error"]["retryable"], false);
+
+        let local: Box<dyn std::error::Error> = "bad flag".into();
+        assert_eq!(error_json(local.as_ref())["error"]["kind"], "error");
+    }
+}
@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use crate::client::backoff;
+
+/// Reconnection state for `--follow` style commands.
+///
+/// A follow loop calls [`Reconnect::wait`] after a failed poll and
+/// [`Reconnect::connected`] after a successful one, so a server restart shows
+/// up as a few "reconnecting" lines on stderr instead of ending the command.
+#[derive(Default)]
+pub struct Reconnect {
+    attempt: u32,
+}
+
+impl Reconnect {
+    /// Note a successful poll, announcing recovery if we were reconnecting.
+    pub fn connected(&mut self) {
+        if self.attempt > 0 {
+            eprintln!("reconnected");
+        }
+        self.attempt = 0;
+    }
+
+    /// Report `err` and sleep before the next try: the server's `hint` if it
+    /// gave one (e.g. `Retry-After`), else exponential backoff.
+    pub async fn wait(&mut self, err: &dyn std::fmt::Display, hint: Option<Duration>) {
+        let delay = hint.unwrap_or_else(|| backoff(self.attempt));
+        eprintln!("connection lost ({err}); reconnecting in {}s", delay.as_secs());
+        tokio::time::sleep(delay).await;
+        self.attempt = self.attempt.saturating_add(1);
+    }
+}
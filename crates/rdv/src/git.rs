@@ -0,0 +1,203 @@
+//! Guarded git and GitHub operations for landing an agent's work: stage,
+//! commit, push the session branch, and open a pull request.
+//!
+//! The guards are the point. Agents run these unattended, so nothing here
+//! force-pushes, commits to the base branch, or stages `.env` files.
+
+use std::path::{Path, PathBuf};
+
+use crate::process::{self, RunOptions};
+
+type BoxError = Box<dyn std::error::Error>;
+
+/// Branches that are never committed to or pushed directly.
+const PROTECTED_BRANCHES: [&str; 2] = ["main", "master"];
+
+/// Pathspecs left out of `stage_all`, at any depth; secrets belong in the
+/// folder's secrets provider.
+const NEVER_STAGE: [&str; 2] = [":(exclude,glob)**/.env", ":(exclude,glob)**/.env.*"];
+
+/// Commit subjects are cut to this many characters.
+const SUBJECT_MAX: usize = 72;
+
+/// A git checkout (main repo or worktree).
+pub struct Repo {
+    root: PathBuf,
+}
+
+impl Repo {
+    /// The checkout containing `dir`.
+    pub async fn open(dir: &Path) -> Result<Self, BoxError> {
+        let root = process::run("git", &["rev-parse", "--show-toplevel"], Some(dir), RunOptions::LOCAL)
+            .await
+            .map_err(|_| format!("{} is not inside a git checkout", dir.display()))?;
+        Ok(Self { root: root.into() })
+    }
+
+    async fn git(&self, args: &[&str]) -> Result<String, BoxError> {
+        Ok(process::run("git", args, Some(&self.root), RunOptions::LOCAL).await?)
+    }
+
+    pub async fn current_branch(&self) -> Result<String, BoxError> {
+        self.git(&["symbolic-ref", "--quiet", "--short", "HEAD"])
+            .await
+            .map_err(|_| "HEAD is detached; check out the session branch first".into())
+    }
+
+    /// `origin`'s default branch, falling back to `main`.
+    pub async fn default_branch(&self) -> String {
+        self.git(&["symbolic-ref", "--short", "refs/remotes/origin/HEAD"])
+            .await
+            .ok()
+            .and_then(|r| r.strip_prefix("origin/").map(String::from))
+            .unwrap_or_else(|| "main".into())
+    }
+
+    /// Paths staged for the next commit.
+    pub async fn staged(&self) -> Result<Vec<String>, BoxError> {
+        let staged = self.git(&["diff", "--cached", "--name-only"]).await?;
+        Ok(staged.lines().map(String::from).collect())
+    }
+
+    /// Stage every change except `.env` files; returns the paths this call
+    /// staged, not ones that were staged already.
+    pub async fn stage_all(&self) -> Result<Vec<String>, BoxError> {
+        let before = self.staged().await?;
+        let mut args = vec!["add", "-A", "--", "."];
+        args.extend(NEVER_STAGE);
+        self.git(&args).await?;
+        let mut after = self.staged().await?;
+        after.retain(|p| !before.contains(p));
+        Ok(after)
+    }
+
+    /// Commit what is staged and return the new commit's hash.
+    pub async fn commit(&self, message: &str) -> Result<String, BoxError> {
+        self.git(&["commit", "--quiet", "-m", message]).await?;
+        self.git(&["rev-parse", "--short", "HEAD"]).await
+    }
+
+    /// Number of commits on HEAD that `base` doesn't have. Compared against
+    /// the remote base when there is one.
+    pub async fn commits_ahead(&self, base: &str) -> Result<u32, BoxError> {
+        let remote = format!("origin/{base}");
+        let base_ref = if self.git(&["rev-parse", "--verify", "--quiet", &remote]).await.is_ok() {
+            remote
+        } else {
+            base.to_string()
+        };
+        let count = self.git(&["rev-list", "--count", &format!("{base_ref}..HEAD")]).await?;
+        Ok(count.parse()?)
+    }
+
+    /// Push `branch` to origin and track it. Never forced: a rejected push
+    /// means someone else moved the branch, and that needs a human.
+    pub async fn push(&self, branch: &str) -> Result<(), BoxError> {
+        check_not_protected(branch)?;
+        let args = ["push", "--quiet", "--set-upstream", "origin", branch];
+        process::run("git", &args, Some(&self.root), RunOptions::NETWORK).await?;
+        Ok(())
+    }
+
+    /// Open a pull request with the `gh` CLI and return its URL.
+    pub async fn open_pr(&self, base: &str, branch: &str, title: &str, body: &str, draft: bool) -> Result<String, BoxError> {
+        let mut args = vec!["pr", "create", "--base", base, "--head", branch, "--title", title, "--body", body];
+        if draft {
+            args.push("--draft");
+        }
+        // Not retried: a create that timed out may still have opened the PR.
+        let opts = RunOptions::NETWORK.retries(0);
+        process::run("gh", &args, Some(&self.root), opts).await.map_err(|e| {
+            format!("{e}\nThe branch is pushed; open the PR by hand or fix `gh auth status` and rerun").into()
+        })
+    }
+}
+
+pub fn check_not_protected(branch: &str) -> Result<(), BoxError> {
+    if PROTECTED_BRANCHES.contains(&branch) {
+        return Err(format!("refusing to commit or push to {branch}; work on a session branch").into());
+    }
+    Ok(())
+}
+
+/// Commit message for a task: its title as the subject, then the
+/// description and a `Task:` trailer so the commit can be traced back.
+pub fn task_commit_message(task_id: &str, title: &str, description: Option<&str>) -> String {
+    let title = title.lines().next().unwrap_or("").trim();
+    let subject = if title.chars().count() > SUBJECT_MAX {
+        let cut: String = title.chars().take(SUBJECT_MAX - 3).collect();
+        format!("{}...", cut.trim_end())
+    } else {
+        title.to_string()
+    };
+    let mut message = subject;
+    if let Some(d) = description.map(str::trim).filter(|d| !d.is_empty()) {
+        message.push_str("\n\n");
+        message.push_str(d);
+    }
+    message.push_str(&format!("\n\nTask: {task_id}"));
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_not_protected, task_commit_message, Repo};
+
+    #[test]
+    fn messages_cut_long_titles_and_trace_the_task() {
+        assert_eq!(
+            task_commit_message("t-1", "Fix login redirect", Some("Loop on expired cookie.\n")),
+            "Fix login redirect\n\nLoop on expired cookie.\n\nTask: t-1"
+        );
+        let long = "x".repeat(100);
+        let subject = task_commit_message("t-2", &long, None).lines().next().unwrap().to_string();
+        assert_eq!(subject.len(), 72);
+        assert!(subject.ends_with("..."));
+    }
+
+    #[test]
+    fn base_branches_are_protected() {
+        assert!(check_not_protected("main").is_err());
+        assert!(check_not_protected("master").is_err());
+        assert!(check_not_protected("feat/login").is_ok());
+    }
+
+    #[tokio::test]
+    async fn stage_all_skips_env_files_at_any_depth() {
+        let root = std::env::temp_dir().join(format!("rdv-git-{}", std::process::id()));
+        let (origin, work) = (root.join("origin.git"), root.join("work"));
+        std::fs::create_dir_all(&work).unwrap();
+        let git = |dir: &std::path::Path, args: &[&str]| {
+            let ok = std::process::Command::new("git")
+                .current_dir(dir)
+                .args(args)
+                .output()
+                .unwrap()
+                .status
+                .success();
+            assert!(ok, "git {args:?}");
+        };
+        git(&root, &["init", "-q", "--bare", "origin.git"]);
+        git(&work, &["init", "-q", "-b", "feat"]);
+        git(&work, &["config", "user.name", "t"]);
+        git(&work, &["config", "user.email", "t@t"]);
+        git(&work, &["remote", "add", "origin", origin.to_str().unwrap()]);
+        std::fs::write(work.join("app.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(work.join(".env"), "TOKEN=1\n").unwrap();
+        std::fs::create_dir_all(work.join("apps/api")).unwrap();
+        std::fs::write(work.join("apps/api/.env"), "TOKEN=2\n").unwrap();
+        std::fs::write(work.join("apps/api/.env.local"), "TOKEN=3\n").unwrap();
+        std::fs::write(work.join("lib.rs"), "\n").unwrap();
+        git(&work, &["add", "lib.rs"]);
+
+        let repo = Repo::open(&work).await.unwrap();
+        assert_eq!(repo.current_branch().await.unwrap(), "feat");
+        assert_eq!(repo.stage_all().await.unwrap(), ["app.rs"]);
+        assert_eq!(repo.staged().await.unwrap(), ["app.rs", "lib.rs"]);
+        repo.commit("Add app").await.unwrap();
+        repo.push("feat").await.unwrap();
+        git(&origin, &["rev-parse", "--verify", "refs/heads/feat"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,9 +1,20 @@
+mod backup;
 mod client;
 mod commands;
 mod config;
+mod dashboard;
+mod error;
+mod follow;
+mod git;
+mod preflight;
+mod process;
+mod provider;
+mod queue;
+mod time;
+mod trace;
 
-use clap::Parser;
-use commands::{agent, browser, channel, context, crown, delegate, group, hook, indicator, migrate, notification, peer, project, screen, send, session, status, system, teams, tmux_compat, worktree};
+use clap::{CommandFactory, Parser};
+use commands::{agent, browser, channel, completions, context, crown, delegate, doctor, github, group, hook, indicator, migrate, note, notification, pause, peer, project, screen, send, session, status, system, task, teams, tmux_compat, tmux_hook, usage, worktree};
 
 #[derive(Parser)]
 #[command(name = "rdv", version, about = "CLI for Remote Dev terminal server")]
@@ -12,6 +23,10 @@ struct Cli {
     #[arg(long, global = true)]
     human: bool,
 
+    /// Emit JSON (the default) and report errors as a JSON object on stderr
+    #[arg(long, global = true, conflicts_with = "human")]
+    json: bool,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -28,21 +43,38 @@ enum Command {
     Group(group::GroupArgs),
     /// Manage projects
     Project(project::ProjectArgs),
+    /// GitHub repository health (open PRs, issues, CI)
+    Github(github::GithubArgs),
     /// Handle Claude Code lifecycle hooks (stop, notify, session-end)
     Hook(hook::HookArgs),
     /// Show dashboard status or report agent status
     Status(status::StatusArgs),
     /// System management (updates, service control)
     System(system::SystemArgs),
+    /// Task helpers for agent worktrees
+    Task(task::TaskArgs),
     /// Show current session context
     Context,
+    /// Check server health, or back up and restore local state
+    Doctor(doctor::DoctorArgs),
+    /// Open the interactive dashboard of sessions
+    Monitor,
+    /// Capture, search and summarize session notes
+    Note(note::NoteArgs),
     /// Manage notifications
     Notification(notification::NotificationArgs),
+    /// Pause automated actions (scheduled commands, agent schedules, GitHub triggers)
+    Pause(pause::PauseArgs),
+    /// Lift an automation pause
+    Resume(pause::ResumeArgs),
     /// Browser automation commands
     Browser(browser::BrowserArgs),
+    /// Claude usage-limit windows for each agent profile
+    Usage(usage::UsageArgs),
     /// Send text or keystrokes to a terminal session
     Send(send::SendArgs),
-    /// Capture terminal screen content
+    /// Capture terminal screen content, once or as a live tail
+    #[command(visible_alias = "peek")]
     Screen(screen::ScreenArgs),
     /// Set a per-session status indicator
     SetStatus(indicator::SetStatusArgs),
@@ -68,13 +100,24 @@ enum Command {
     Migrate(migrate::MigrateArgs),
     /// tmux compatibility layer
     Tmux(tmux_compat::TmuxCompatArgs),
+    /// Read and write the rdv settings file
+    Config(commands::config::ConfigArgs),
+    /// Print a shell completion script (source it from your shell's rc file)
+    Completions(completions::CompletionsArgs),
+    /// Print the man page, or write one per subcommand with --dir
+    Man(completions::ManArgs),
+    /// Callback run by tmux hooks to report pane activity
+    #[command(name = "_hook", hide = true)]
+    TmuxHook(tmux_hook::TmuxHookArgs),
 }
 
 #[tokio::main]
 async fn main() {
+    clap_complete::CompleteEnv::with_factory(Cli::command).complete();
     let cli = Cli::parse();
     let cfg = config::ServerConfig::from_env();
     let client = client::Client::new(&cfg);
+    let json_errors = cli.json;
 
     let result = match cli.command {
         Command::Session(args) => session::run(args, &client, cli.human).await,
@@ -82,12 +125,20 @@ async fn main() {
         Command::Agent(args) => agent::run(args, &client, cli.human).await,
         Command::Group(args) => group::run(args, &client, cli.human).await,
         Command::Project(args) => project::run(args, &client, cli.human).await,
+        Command::Github(args) => github::run(args, &client, cli.human).await,
         Command::Hook(args) => hook::run(args, &client, cli.human).await,
         Command::Status(args) => status::run(args, &client, cli.human).await,
         Command::System(args) => system::run(args, &client, cli.human).await,
+        Command::Task(args) => task::run(args, &client, cli.human).await,
         Command::Context => context::run(&client, cli.human).await,
+        Command::Doctor(args) => doctor::run(args, &client, cli.human).await,
+        Command::Monitor => dashboard::run(&client).await,
+        Command::Note(args) => note::run(args, &client, cli.human).await,
         Command::Notification(args) => notification::run(args, &client, cli.human).await,
+        Command::Pause(args) => pause::run_pause(args, &client, cli.human).await,
+        Command::Resume(args) => pause::run_resume(args, &client, cli.human).await,
         Command::Browser(args) => browser::run(args, &client, cli.human).await,
+        Command::Usage(args) => usage::run(args, &client, cli.human).await,
         Command::Send(args) => send::run(args, &client).await,
         Command::Screen(args) => screen::run(args, &client, cli.human).await,
         Command::SetStatus(args) => indicator::run_set_status(args, &client).await,
@@ -102,10 +153,18 @@ async fn main() {
         Command::Delegate(args) => delegate::run(args, cli.human).await,
         Command::Migrate(args) => migrate::run(args, &client, cli.human).await,
         Command::Tmux(args) => tmux_compat::run(args, &client, cli.human).await,
+        Command::TmuxHook(args) => tmux_hook::run(args, &client, cli.human).await,
+        Command::Config(args) => commands::config::run(args, &client, cli.human).await,
+        Command::Completions(args) => completions::run_completions(args),
+        Command::Man(args) => completions::run_man(args, Cli::command()),
     };
 
     if let Err(e) = result {
-        eprintln!("Error: {e}");
+        if json_errors {
+            eprintln!("{}", error::error_json(e.as_ref()));
+        } else {
+            eprintln!("Error: {e}");
+        }
         std::process::exit(1);
     }
 }
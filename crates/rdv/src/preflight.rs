@@ -0,0 +1,225 @@
+//! Local pre-flight checks, run before `rdv worktree create` and by
+//! `rdv doctor`, so a missing tool or a full disk is reported up front
+//! instead of halfway through a checkout.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::process::{self, RunOptions};
+use crate::provider::Provider;
+
+/// Oldest git with the `worktree` subcommands the server relies on
+/// (`worktree list --porcelain`, `worktree remove`).
+const MIN_GIT_VERSION: (u32, u32) = (2, 17);
+/// Free space to keep beyond the estimated checkout size.
+const DISK_HEADROOM_KIB: u64 = 512 * 1024;
+
+#[derive(Debug, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub ok: bool,
+    /// Worth showing but not a failure: `ok` stays true.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub warning: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl Check {
+    pub fn pass(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            ok: true,
+            warning: false,
+            detail: None,
+        }
+    }
+
+    pub fn pass_with(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: true,
+            warning: false,
+            detail: Some(detail.into()),
+        }
+    }
+
+    pub fn warn(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: true,
+            warning: true,
+            detail: Some(detail.into()),
+        }
+    }
+
+    pub fn fail(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ok: false,
+            warning: false,
+            detail: Some(detail.into()),
+        }
+    }
+}
+
+/// Run a command and return trimmed stdout, or `None` if it is missing or
+/// exits non-zero.
+async fn output(program: &str, args: &[&str]) -> Option<String> {
+    process::run(program, args, None, RunOptions::LOCAL).await.ok()
+}
+
+/// Parse `git version 2.39.3 (Apple Git-146)` into `(2, 39)`.
+fn parse_git_version(text: &str) -> Option<(u32, u32)> {
+    let version = text.strip_prefix("git version ")?.split_whitespace().next()?;
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Available KiB from `df -Pk` output (fourth column of the data row).
+fn parse_df_available(text: &str) -> Option<u64> {
+    text.lines().nth(1)?.split_whitespace().nth(3)?.parse().ok()
+}
+
+/// Repository object size in KiB from `git count-objects -v`
+/// (`size` for loose objects plus `size-pack`).
+fn parse_count_objects(text: &str) -> Option<u64> {
+    let mut total = None;
+    for line in text.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            if key == "size" || key == "size-pack" {
+                total = Some(total.unwrap_or(0) + value.trim().parse::<u64>().ok()?);
+            }
+        }
+    }
+    total
+}
+
+fn uses_lfs(repo: &Path) -> bool {
+    std::fs::read_to_string(repo.join(".gitattributes"))
+        .map(|a| a.contains("filter=lfs"))
+        .unwrap_or(false)
+}
+
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+        .unwrap_or(false)
+}
+
+async fn check_git() -> Check {
+    let Some(text) = output("git", &["--version"]).await else {
+        return Check::fail("git", "git is not installed or not on PATH");
+    };
+    match parse_git_version(&text) {
+        Some(v) if v >= MIN_GIT_VERSION => Check::pass_with("git", format!("{}.{}", v.0, v.1)),
+        Some(v) => Check::fail(
+            "git",
+            format!(
+                "git {}.{} is too old; worktrees need {}.{} or newer",
+                v.0, v.1, MIN_GIT_VERSION.0, MIN_GIT_VERSION.1
+            ),
+        ),
+        None => Check::fail("git", format!("unrecognized `git --version` output: {text}")),
+    }
+}
+
+async fn check_lfs(repo: &Path) -> Option<Check> {
+    if !uses_lfs(repo) {
+        return None;
+    }
+    Some(match output("git", &["lfs", "version"]).await {
+        Some(_) => Check::pass("git-lfs"),
+        None => Check::fail(
+            "git-lfs",
+            "repository tracks files with Git LFS but git-lfs is not installed; install it and run `git lfs install`",
+        ),
+    })
+}
+
+/// Compare free space where `dir` lives against `needed_kib` plus headroom.
+async fn check_disk(dir: &Path, needed_kib: u64) -> Check {
+    let dir_str = dir.to_string_lossy();
+    let Some(available) = output("df", &["-Pk", &dir_str]).await.as_deref().and_then(parse_df_available)
+    else {
+        return Check::pass_with("disk", "free space unknown (df unavailable)");
+    };
+    let required = needed_kib + DISK_HEADROOM_KIB;
+    if available >= required {
+        Check::pass_with("disk", format!("{} MiB free", available / 1024))
+    } else {
+        Check::fail(
+            "disk",
+            format!(
+                "{} MiB free on {}, need about {} MiB; free up space before creating a worktree",
+                available / 1024,
+                dir.display(),
+                required / 1024
+            ),
+        )
+    }
+}
+
+/// A host without any agent CLI can still run shells and talk to the server,
+/// so that's a warning rather than a failure.
+fn check_providers() -> Check {
+    let found: Vec<&str> = Provider::ALL.iter().map(|p| p.binary()).filter(|b| on_path(b)).collect();
+    if found.is_empty() {
+        let looked: Vec<&str> = Provider::ALL.iter().map(|p| p.binary()).collect();
+        Check::warn(
+            "providers",
+            format!("no agent CLI found on PATH (looked for {})", looked.join(", ")),
+        )
+    } else {
+        Check::pass_with("providers", found.join(", "))
+    }
+}
+
+/// Checks before creating a worktree of `repo`: git version, LFS if the
+/// repository needs it, and room for another checkout.
+pub async fn for_worktree(repo: &Path) -> Vec<Check> {
+    let mut checks = vec![check_git().await];
+    checks.extend(check_lfs(repo).await);
+    let repo_str = repo.to_string_lossy();
+    let estimate = output("git", &["-C", &repo_str, "count-objects", "-v"])
+        .await
+        .as_deref()
+        .and_then(parse_count_objects)
+        .unwrap_or(0);
+    checks.push(check_disk(repo, estimate).await);
+    checks
+}
+
+/// Machine-level checks for `rdv doctor`.
+pub async fn for_host(data_dir: &Path) -> Vec<Check> {
+    let dir = if data_dir.exists() { data_dir } else { Path::new(".") };
+    vec![check_git().await, check_disk(dir, 0).await, check_providers()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_count_objects, parse_df_available, parse_git_version};
+
+    #[test]
+    fn parses_git_versions() {
+        assert_eq!(parse_git_version("git version 2.39.3 (Apple Git-146)"), Some((2, 39)));
+        assert_eq!(parse_git_version("git version 2.17.1"), Some((2, 17)));
+        assert_eq!(parse_git_version("hub version 2.14"), None);
+    }
+
+    #[test]
+    fn parses_df_available_column() {
+        let df = "Filesystem 1024-blocks Used Available Capacity Mounted on\n/dev/sda1 1000000 400000 600000 40% /\n";
+        assert_eq!(parse_df_available(df), Some(600000));
+        assert_eq!(parse_df_available(""), None);
+    }
+
+    #[test]
+    fn sums_loose_and_packed_object_sizes() {
+        let out = "count: 12\nsize: 48\nin-pack: 900\npacks: 1\nsize-pack: 2048\nprune-packable: 0\ngarbage: 0\nsize-garbage: 0\n";
+        assert_eq!(parse_count_objects(out), Some(2096));
+    }
+}
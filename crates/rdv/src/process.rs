@@ -0,0 +1,167 @@
+//! External commands (git, tmux, sqlite3, tar, gh) with a timeout and, for
+//! calls that touch the network, retries with backoff.
+//!
+//! A hung `git fetch` against a dead remote would otherwise block the CLI,
+//! and the hook or agent that invoked it, forever. Children are killed when
+//! their future is dropped, so a timeout or Ctrl-C doesn't leave them behind.
+
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use tokio::process::Command;
+
+use crate::client::backoff;
+
+/// How long a command may run, and how often a transient failure is retried.
+#[derive(Debug, Clone, Copy)]
+pub struct RunOptions {
+    pub timeout: Duration,
+    pub retries: u32,
+}
+
+impl RunOptions {
+    /// Local, quick commands: `git rev-parse`, `tmux set-hook`, `df`.
+    pub const LOCAL: Self = Self {
+        timeout: Duration::from_secs(30),
+        retries: 0,
+    };
+    /// Commands that talk to a remote: `git push`, `git clone`.
+    pub const NETWORK: Self = Self {
+        timeout: Duration::from_secs(120),
+        retries: 2,
+    };
+
+    pub fn timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+
+    pub fn retries(self, retries: u32) -> Self {
+        Self { retries, ..self }
+    }
+}
+
+#[derive(Debug)]
+pub enum ProcessError {
+    /// The program couldn't be started (usually: not installed).
+    Spawn { program: String, error: std::io::Error },
+    TimedOut { command: String, after: Duration },
+    /// Exited non-zero.
+    Failed { command: String, stderr: String },
+}
+
+impl ProcessError {
+    /// Worth running again: timeouts and the network errors git reports.
+    fn is_transient(&self) -> bool {
+        match self {
+            ProcessError::TimedOut { .. } => true,
+            ProcessError::Failed { stderr, .. } => TRANSIENT_STDERR.iter().any(|m| stderr.contains(m)),
+            ProcessError::Spawn { .. } => false,
+        }
+    }
+}
+
+/// stderr fragments that mean the remote, not the request, was the problem.
+const TRANSIENT_STDERR: [&str; 6] = [
+    "Could not resolve host",
+    "Connection timed out",
+    "Connection reset",
+    "Connection refused",
+    "The remote end hung up unexpectedly",
+    "early EOF",
+];
+
+impl fmt::Display for ProcessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessError::Spawn { program, error } => write!(f, "cannot run {program}: {error}"),
+            ProcessError::TimedOut { command, after } => {
+                write!(f, "{command} timed out after {}s", after.as_secs())
+            }
+            ProcessError::Failed { command, stderr, .. } => write!(f, "{command} failed: {stderr}"),
+        }
+    }
+}
+
+impl std::error::Error for ProcessError {}
+
+/// Run `program` with `args` (in `dir` when given) and return trimmed stdout.
+pub async fn run(program: &str, args: &[&str], dir: Option<&Path>, opts: RunOptions) -> Result<String, ProcessError> {
+    let mut attempt = 0;
+    loop {
+        match run_once(program, args, dir, opts.timeout).await {
+            Err(e) if e.is_transient() && attempt < opts.retries => {
+                tokio::time::sleep(backoff(attempt)).await;
+                attempt += 1;
+            }
+            other => return other,
+        }
+    }
+}
+
+async fn run_once(program: &str, args: &[&str], dir: Option<&Path>, timeout: Duration) -> Result<String, ProcessError> {
+    // Name errors by program and subcommand ("git push"), not the full argv,
+    // which can hold commit messages and PR bodies.
+    let command = match args.first() {
+        Some(sub) => format!("{program} {sub}"),
+        None => program.to_string(),
+    };
+    let mut cmd = Command::new(program);
+    cmd.args(args).kill_on_drop(true);
+    if let Some(dir) = dir {
+        cmd.current_dir(dir);
+    }
+    let out = match tokio::time::timeout(timeout, cmd.output()).await {
+        Err(_) => return Err(ProcessError::TimedOut { command, after: timeout }),
+        Ok(Err(error)) => {
+            return Err(ProcessError::Spawn {
+                program: program.to_string(),
+                error,
+            })
+        }
+        Ok(Ok(out)) => out,
+    };
+    if !out.status.success() {
+        return Err(ProcessError::Failed {
+            command,
+            stderr: String::from_utf8_lossy(&out.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{run, ProcessError, RunOptions};
+
+    #[tokio::test]
+    async fn hung_commands_time_out() {
+        let started = Instant::now();
+        let opts = RunOptions::LOCAL.timeout(Duration::from_millis(200));
+        let err = run("sleep", &["5"], None, opts).await.unwrap_err();
+        assert!(matches!(err, ProcessError::TimedOut { .. }));
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn failures_keep_stderr_and_missing_programs_are_not_retried() {
+        let err = run("sh", &["-c", "echo nope >&2; exit 3"], None, RunOptions::LOCAL).await.unwrap_err();
+        assert_eq!(err.to_string(), "sh -c failed: nope");
+        assert!(matches!(err, ProcessError::Failed { .. }));
+
+        let err = run("rdv-no-such-program", &[], None, RunOptions::NETWORK).await.unwrap_err();
+        assert!(matches!(err, ProcessError::Spawn { .. }));
+    }
+
+    #[test]
+    fn network_errors_are_transient() {
+        let failed = |stderr: &str| ProcessError::Failed {
+            command: "git push".into(),
+            stderr: stderr.into(),
+        };
+        assert!(failed("fatal: unable to access: Could not resolve host: github.com").is_transient());
+        assert!(!failed("! [rejected] feat -> feat (non-fast-forward)").is_transient());
+    }
+}
@@ -0,0 +1,110 @@
+//! What the CLI knows about each agent provider: the binary it runs, how to
+//! tell from a screen capture that it has finished booting, and how prompt
+//! text must be escaped so typing it into the pane doesn't submit early.
+
+use std::fmt;
+
+/// An agent CLI a session can run. The wire name matches the server's
+/// `agentProvider` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    Claude,
+    Codex,
+    Gemini,
+    OpenCode,
+}
+
+impl Provider {
+    pub const ALL: [Provider; 4] = [Provider::Claude, Provider::Codex, Provider::Gemini, Provider::OpenCode];
+
+    /// Wire names, for `value_parser` lists.
+    pub const NAMES: [&'static str; 4] = ["claude", "codex", "gemini", "opencode"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Provider::Claude => "claude",
+            Provider::Codex => "codex",
+            Provider::Gemini => "gemini",
+            Provider::OpenCode => "opencode",
+        }
+    }
+
+    /// Executable looked up on PATH.
+    pub fn binary(self) -> &'static str {
+        self.name()
+    }
+
+    /// Text each CLI draws once its input box accepts keystrokes. Anything
+    /// typed before then is lost or lands in a startup prompt.
+    fn ready_markers(self) -> &'static [&'static str] {
+        match self {
+            Provider::Claude => &["? for shortcuts", "bypass permissions"],
+            Provider::Codex => &["\u{23ce} send", "send a message"],
+            Provider::Gemini => &["Type your message"],
+            Provider::OpenCode => &["ctrl+p commands", "enter send"],
+        }
+    }
+
+    /// Whether a screen capture shows the CLI ready for input.
+    pub fn is_ready(self, screen: &str) -> bool {
+        self.ready_markers().iter().any(|m| screen.contains(m))
+    }
+
+    /// Make `text` safe to type as one prompt. Control characters are
+    /// dropped. A bare newline would submit, so Claude gets its
+    /// backslash-newline continuation and the others get a space.
+    pub fn escape_prompt(self, text: &str) -> String {
+        let newline = match self {
+            Provider::Claude => "\\\n",
+            Provider::Codex | Provider::Gemini | Provider::OpenCode => " ",
+        };
+        let mut out = String::with_capacity(text.len());
+        for c in text.trim_end().chars() {
+            match c {
+                '\n' => out.push_str(newline),
+                '\t' => out.push(' '),
+                c if c.is_control() => {}
+                c => out.push(c),
+            }
+        }
+        out
+    }
+}
+
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Provider;
+
+    #[test]
+    fn names_round_trip() {
+        for p in Provider::ALL {
+            assert_eq!(Provider::from_name(p.name()), Some(p));
+        }
+        assert_eq!(Provider::NAMES.map(Provider::from_name).map(Option::unwrap), Provider::ALL);
+        assert_eq!(Provider::from_name("claude-code"), None);
+    }
+
+    #[test]
+    fn readiness_needs_the_input_box() {
+        assert!(!Provider::Claude.is_ready("Welcome to Claude Code\nLoading..."));
+        assert!(Provider::Claude.is_ready("> \n  ? for shortcuts"));
+        assert!(!Provider::Gemini.is_ready("? for shortcuts"));
+    }
+
+    #[test]
+    fn newlines_never_submit_early() {
+        assert_eq!(Provider::Claude.escape_prompt("fix it\nthen test\n"), "fix it\\\nthen test");
+        assert_eq!(Provider::Codex.escape_prompt("fix it\nthen test"), "fix it then test");
+        assert_eq!(Provider::Gemini.escape_prompt("a\u{1b}[2Jb\tc"), "a[2Jb c");
+    }
+}
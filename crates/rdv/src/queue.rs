@@ -0,0 +1,153 @@
+//! Offline journal for mutating API calls.
+//!
+//! With `RDV_OFFLINE_QUEUE=1`, calls that are safe to deliver late (notes,
+//! captures) are appended here when the server is unreachable instead of
+//! failing the command. The journal is replayed in order by the next queued
+//! call that gets through, or explicitly by `rdv doctor --flush-queue`.
+//!
+//! Several rdv processes may share the journal (agents in parallel panes), so
+//! appends and a flush's read-replay-rewrite hold `queue.jsonl.lock`.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::data_dir;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedCall {
+    pub method: String,
+    pub path: String,
+    pub body: serde_json::Value,
+    pub queued_at: String,
+}
+
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    /// The journal under the local data directory (`rdv/queue.jsonl`).
+    pub fn open_default() -> Self {
+        Self::at(data_dir().join("rdv/queue.jsonl"))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Block until this process holds the journal's lock; released when the
+    /// returned file is dropped. Not reentrant.
+    pub fn lock(&self) -> io::Result<File> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.path.with_extension("jsonl.lock"))?;
+        file.lock()?;
+        Ok(file)
+    }
+
+    /// Add `call` to the end of the journal; `_lock` is the caller's
+    /// [`Journal::lock`], held from the `load` that decided to queue it.
+    pub fn append(&self, _lock: &File, call: &QueuedCall) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(call)?)
+    }
+
+    /// Every queued call, oldest first. Lines that fail to parse (a torn
+    /// write) are skipped, with a warning, rather than blocking the rest of
+    /// the queue.
+    pub fn load(&self) -> io::Result<Vec<QueuedCall>> {
+        let text = match fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut calls = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            match serde_json::from_str(line) {
+                Ok(call) => calls.push(call),
+                Err(e) => eprintln!("rdv: skipping unreadable line {} of {}: {e}", n + 1, self.path.display()),
+            }
+        }
+        Ok(calls)
+    }
+
+    /// Atomically replace the journal with `calls` (removing it when empty).
+    /// Hold [`Journal::lock`] from the `load` this is based on.
+    pub fn replace(&self, calls: &[QueuedCall]) -> io::Result<()> {
+        if calls.is_empty() {
+            return match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        let tmp = self.path.with_extension("jsonl.tmp");
+        let mut text = String::new();
+        for call in calls {
+            text.push_str(&serde_json::to_string(call)?);
+            text.push('\n');
+        }
+        fs::write(&tmp, text)?;
+        fs::rename(tmp, &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Journal, QueuedCall};
+
+    fn call(path: &str) -> QueuedCall {
+        QueuedCall {
+            method: "POST".into(),
+            path: path.into(),
+            body: serde_json::json!({ "body": "hi" }),
+            queued_at: "2026-01-01T00:00:00Z".into(),
+        }
+    }
+
+    #[test]
+    fn append_load_and_replace_round_trip() {
+        let dir = std::env::temp_dir().join(format!("rdv-queue-{}", std::process::id()));
+        let journal = Journal::at(dir.join("queue.jsonl"));
+        assert!(journal.load().unwrap().is_empty());
+
+        let lock = journal.lock().unwrap();
+        journal.append(&lock, &call("/api/a")).unwrap();
+        journal.append(&lock, &call("/api/b")).unwrap();
+        assert_eq!(journal.load().unwrap(), vec![call("/api/a"), call("/api/b")]);
+
+        journal.replace(&[call("/api/b")]).unwrap();
+        assert_eq!(journal.load().unwrap(), vec![call("/api/b")]);
+
+        journal.replace(&[]).unwrap();
+        assert!(journal.load().unwrap().is_empty());
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn lock_excludes_other_handles_and_torn_lines_are_skipped() {
+        let dir = std::env::temp_dir().join(format!("rdv-queue-lock-{}", std::process::id()));
+        let journal = Journal::at(dir.join("queue.jsonl"));
+        let held = journal.lock().unwrap();
+        let other = std::fs::File::open(dir.join("queue.jsonl.lock")).unwrap();
+        assert!(other.try_lock().is_err());
+        drop(held);
+        assert!(other.try_lock().is_ok());
+        drop(other);
+
+        journal.append(&journal.lock().unwrap(), &call("/api/a")).unwrap();
+        let mut text = std::fs::read_to_string(dir.join("queue.jsonl")).unwrap();
+        text.push_str("{\"method\":\"PO\n");
+        std::fs::write(dir.join("queue.jsonl"), text).unwrap();
+        assert_eq!(journal.load().unwrap(), vec![call("/api/a")]);
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
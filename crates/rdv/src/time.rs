@@ -0,0 +1,138 @@
+use std::env;
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// Timezone used when rendering timestamps for humans.
+///
+/// The servers speak UTC (RFC 3339 strings or epoch millis); the CLI converts
+/// at the edge so every command shows the same wall-clock time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisplayZone {
+    /// The machine's local timezone.
+    Local,
+    Utc,
+    /// An IANA zone such as `Europe/Berlin`.
+    Named(Tz),
+}
+
+impl DisplayZone {
+    /// Resolve from `RDV_TIMEZONE`, else the settings file's `timezone`
+    /// (`local`, `UTC`, or an IANA name). An unrecognized `RDV_TIMEZONE` is
+    /// passed over for the file; with neither, the machine's local timezone.
+    pub fn from_env() -> Self {
+        env::var("RDV_TIMEZONE")
+            .ok()
+            .and_then(|v| Self::parse(&v))
+            .or_else(|| crate::config::file_settings().timezone.as_deref().and_then(Self::parse))
+            .unwrap_or(DisplayZone::Local)
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("local") {
+            Some(DisplayZone::Local)
+        } else if value.eq_ignore_ascii_case("utc") || value == "Z" {
+            Some(DisplayZone::Utc)
+        } else {
+            value.parse::<Tz>().ok().map(DisplayZone::Named)
+        }
+    }
+
+    fn render(&self, ts: DateTime<Utc>) -> String {
+        match self {
+            DisplayZone::Local => ts.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S %:z").to_string(),
+            DisplayZone::Utc => ts.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+            DisplayZone::Named(tz) => ts.with_timezone(tz).format("%Y-%m-%d %H:%M:%S %Z").to_string(),
+        }
+    }
+}
+
+/// Parse a server timestamp: RFC 3339, or epoch milliseconds as digits.
+pub fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(t) = DateTime::parse_from_rfc3339(raw) {
+        return Some(t.with_timezone(&Utc));
+    }
+    raw.parse::<i64>()
+        .ok()
+        .and_then(|ms| Utc.timestamp_millis_opt(ms).single())
+}
+
+/// Render a server timestamp in the configured display zone. Unparseable
+/// input is returned unchanged rather than hidden.
+pub fn format_timestamp(raw: &str) -> String {
+    format_timestamp_in(raw, DisplayZone::from_env())
+}
+
+pub fn format_timestamp_in(raw: &str, zone: DisplayZone) -> String {
+    match parse_timestamp(raw) {
+        Some(ts) => zone.render(ts),
+        None => raw.to_string(),
+    }
+}
+
+/// Render a timestamp relative to now ("5m ago", "2h 3m ago", "4d ago").
+pub fn format_relative_time(raw: &str) -> String {
+    let Some(ts) = parse_timestamp(raw) else {
+        return raw.to_string();
+    };
+    let secs = Utc::now().signed_duration_since(ts).num_seconds();
+    if secs < 60 {
+        format!("{}s ago", secs)
+    } else if secs < 3600 {
+        format!("{}m ago", secs / 60)
+    } else if secs < 86400 {
+        let hours = secs / 3600;
+        let mins = (secs % 3600) / 60;
+        if mins > 0 {
+            format!("{}h {}m ago", hours, mins)
+        } else {
+            format!("{}h ago", hours)
+        }
+    } else {
+        let days = secs / 86400;
+        format!("{}d ago", days)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_timestamp_in, parse_timestamp, DisplayZone};
+
+    #[test]
+    fn parses_rfc3339_and_epoch_millis_to_same_instant() {
+        let iso = parse_timestamp("2026-03-01T12:00:00Z").unwrap();
+        let millis = parse_timestamp("1772366400000").unwrap();
+        assert_eq!(iso, millis);
+    }
+
+    #[test]
+    fn renders_in_named_zone_with_abbreviation() {
+        let zone = DisplayZone::parse("America/New_York").unwrap();
+        assert_eq!(
+            format_timestamp_in("2026-03-01T12:00:00Z", zone),
+            "2026-03-01 07:00:00 EST"
+        );
+    }
+
+    #[test]
+    fn renders_utc() {
+        assert_eq!(
+            format_timestamp_in("2026-03-01T12:00:00+02:00", DisplayZone::Utc),
+            "2026-03-01 10:00:00 UTC"
+        );
+    }
+
+    #[test]
+    fn leaves_unparseable_input_alone() {
+        assert_eq!(format_timestamp_in("yesterday", DisplayZone::Utc), "yesterday");
+    }
+
+    #[test]
+    fn parse_zone_names() {
+        assert_eq!(DisplayZone::parse("utc"), Some(DisplayZone::Utc));
+        assert_eq!(DisplayZone::parse("LOCAL"), Some(DisplayZone::Local));
+        assert_eq!(DisplayZone::parse("Mars/Olympus"), None);
+    }
+}
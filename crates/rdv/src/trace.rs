@@ -0,0 +1,75 @@
+//! Request correlation for server-side tracing.
+//!
+//! Every request carries an `x-request-id` and a W3C `traceparent` header.
+//! All calls made by one `rdv` invocation share a trace ID, so the server's
+//! spans for a multi-call command (say `worktree create` then `session
+//! create`) group under one trace. With `RDV_TRACE=1` each call's status
+//! and latency are also printed to stderr.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// 64 random bits. std's hasher keys are seeded per process; mixing in a
+/// counter keeps successive values distinct.
+pub(crate) fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.finish()
+}
+
+#[derive(Debug, Clone)]
+pub struct Trace {
+    trace_id: String,
+    /// Log each call to stderr (`RDV_TRACE=1`).
+    pub verbose: bool,
+}
+
+impl Trace {
+    pub fn new(verbose: bool) -> Self {
+        Self {
+            trace_id: format!("{:016x}{:016x}", random_u64(), random_u64()),
+            verbose,
+        }
+    }
+
+    /// A fresh span ID, also used as the request ID.
+    pub fn span_id(&self) -> String {
+        format!("{:016x}", random_u64())
+    }
+
+    pub fn traceparent(&self, span_id: &str) -> String {
+        format!("00-{}-{span_id}-01", self.trace_id)
+    }
+
+    pub fn log(&self, method: &str, path: &str, status: Option<u16>, elapsed: Duration, request_id: &str) {
+        if !self.verbose {
+            return;
+        }
+        let status = status.map_or_else(|| "ERR".to_string(), |s| s.to_string());
+        eprintln!(
+            "rdv: {method} {path} -> {status} in {}ms (request {request_id})",
+            elapsed.as_millis()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Trace;
+
+    #[test]
+    fn traceparent_shares_trace_id_across_spans() {
+        let trace = Trace::new(false);
+        let a = trace.traceparent(&trace.span_id());
+        let b = trace.traceparent(&trace.span_id());
+        let parts: Vec<&str> = a.split('-').collect();
+        assert_eq!(parts.len(), 4);
+        assert_eq!((parts[0], parts[1].len(), parts[2].len(), parts[3]), ("00", 32, 16, "01"));
+        assert_eq!(a[..36], b[..36]);
+        assert_ne!(a, b);
+    }
+}
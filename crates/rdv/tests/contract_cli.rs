@@ -0,0 +1,758 @@
+//! Contract tests: run the real binary against a stub HTTP server and check
+//! both halves of the exchange — the method, path, headers and body the CLI
+//! sends, and that it decodes the response shape the Next.js routes return.
+//!
+//! Every request a test sees, and every path literal in the CLI source, must
+//! name a route the server actually serves: a handler exported from
+//! `src/app/api/**/route.ts`, or one the terminal server matches in
+//! `handleInternalApi`. A fixture must also match that handler's response
+//! shape (and its `src/types` interface), so a change to either side shows
+//! up here.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::thread::{self, JoinHandle};
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use predicates::str::contains;
+
+/// One request as seen by the stub server.
+#[derive(Debug)]
+struct Captured {
+    request_line: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Captured {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.body).expect("request body is JSON")
+    }
+}
+
+/// Serve `responses` in order, one connection each, returning the port and a
+/// handle yielding every captured request.
+fn stub(responses: Vec<(u16, &'static str)>) -> (u16, JoinHandle<Vec<Captured>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = thread::spawn(move || {
+        let mut captured = Vec::new();
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                let (k, v) = line.split_once(':').unwrap();
+                headers.push((k.trim().to_string(), v.trim().to_string()));
+            }
+            let len = headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case("content-length"))
+                .map(|(_, v)| v.parse::<usize>().unwrap())
+                .unwrap_or(0);
+            let mut buf = vec![0; len];
+            reader.read_exact(&mut buf).unwrap();
+
+            let extra = if status == 429 { "Retry-After: 0\r\n" } else { "" };
+            let mut out = stream;
+            write!(
+                out,
+                "HTTP/1.1 {status} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{extra}Connection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            captured.push(Captured {
+                request_line: request_line.trim_end().to_string(),
+                headers,
+                body: String::from_utf8(buf).unwrap(),
+            });
+        }
+        captured
+    });
+    (port, handle)
+}
+
+/// Assert the CLI sent exactly these requests, as `METHOD /path?query`, in
+/// order, and that the server has a handler for each.
+fn assert_requests(reqs: &[Captured], expected: &[&str]) {
+    let sent: Vec<&str> = reqs
+        .iter()
+        .map(|r| r.request_line.strip_suffix(" HTTP/1.1").unwrap_or(&r.request_line))
+        .collect();
+    assert_eq!(sent, expected);
+    for line in sent {
+        let (method, target) = line.split_once(' ').unwrap();
+        let path = target.split('?').next().unwrap();
+        assert!(
+            served_routes().iter().any(|r| r.method.as_deref().is_none_or(|m| m == method) && r.matches(path)),
+            "{line} has no handler on the server"
+        );
+    }
+}
+
+/// A route the server serves. `method` is None when the handler accepts any.
+#[derive(Debug)]
+struct Route {
+    method: Option<String>,
+    /// Path segments; `[id]` matches one segment, `[...rest]` one or more,
+    /// `[[...rest]]` zero or more.
+    segments: Vec<String>,
+}
+
+impl Route {
+    fn new(method: Option<&str>, path: &str) -> Self {
+        Route {
+            method: method.map(str::to_string),
+            segments: path.split('/').filter(|s| !s.is_empty()).map(str::to_string).collect(),
+        }
+    }
+
+    /// Whether `path` hits this route. A `{}` segment (a `format!`
+    /// placeholder) only matches a dynamic one.
+    fn matches(&self, path: &str) -> bool {
+        let parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let mut i = 0;
+        for seg in &self.segments {
+            if seg.starts_with("[[...") {
+                return true;
+            }
+            if seg.starts_with("[...") {
+                return parts.len() > i;
+            }
+            let Some(part) = parts.get(i) else { return false };
+            let dynamic = seg.starts_with('[');
+            if !dynamic && (part.starts_with('{') || part != seg) {
+                return false;
+            }
+            i += 1;
+        }
+        i == parts.len()
+    }
+}
+
+fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+/// Next.js handlers under `src/app/api`, plus the terminal server's.
+fn served_routes() -> &'static [Route] {
+    static ROUTES: OnceLock<Vec<Route>> = OnceLock::new();
+    ROUTES.get_or_init(|| {
+        let mut routes = Vec::new();
+        let api = repo_root().join("src/app");
+        collect_next_routes(&api, &api, &mut routes);
+        collect_terminal_routes(&repo_root().join("src/server/terminal.ts"), &mut routes);
+        assert!(!routes.is_empty(), "no server routes found under {}", api.display());
+        routes
+    })
+}
+
+fn collect_next_routes(root: &Path, dir: &Path, routes: &mut Vec<Route>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_next_routes(root, &path, routes);
+            continue;
+        }
+        if path.file_name().is_none_or(|n| n != "route.ts") {
+            continue;
+        }
+        // Route groups (`(name)`) don't appear in the URL.
+        let url: String = path
+            .parent()
+            .unwrap()
+            .strip_prefix(root)
+            .unwrap()
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .filter(|c| !(c.starts_with('(') && c.ends_with(')')))
+            .map(|c| format!("/{c}"))
+            .collect();
+        let source = fs::read_to_string(&path).unwrap();
+        for method in ["GET", "POST", "PUT", "PATCH", "DELETE"] {
+            let exported = [
+                format!("export const {method} "),
+                format!("export async function {method}("),
+                format!("export function {method}("),
+            ]
+            .iter()
+            .any(|decl| source.contains(decl.as_str()));
+            if exported {
+                routes.push(Route::new(Some(method), &url));
+            }
+        }
+    }
+}
+
+/// `pathname === "/x" && req.method === "POST"` checks, and the
+/// `pathname?.startsWith("/internal/scheduler/")` action prefixes.
+fn collect_terminal_routes(path: &Path, routes: &mut Vec<Route>) {
+    let source = fs::read_to_string(path).unwrap();
+    for line in source.lines() {
+        if let Some(rest) = line.split("pathname === \"").nth(1) {
+            let route = rest.split('"').next().unwrap();
+            let method = line.split("req.method === \"").nth(1).and_then(|m| m.split('"').next());
+            routes.push(Route::new(method, route));
+        } else if let Some(rest) = line.split("pathname?.startsWith(\"").nth(1) {
+            let prefix = rest.split('"').next().unwrap();
+            routes.push(Route::new(None, &format!("{prefix}[...action]")));
+        }
+    }
+}
+
+/// Path literals (`"/api/..."`, `"/internal/..."`, including `format!`
+/// templates) in the CLI's non-test source.
+fn cli_paths(dir: &Path, found: &mut Vec<(String, String)>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            cli_paths(&path, found);
+            continue;
+        }
+        if path.extension().is_none_or(|e| e != "rs") {
+            continue;
+        }
+        let source = fs::read_to_string(&path).unwrap();
+        let source = source.split("#[cfg(test)]").next().unwrap();
+        for (n, line) in source.lines().enumerate() {
+            if line.contains("starts_with(") {
+                continue;
+            }
+            for prefix in ["\"/api/", "\"/internal/"] {
+                for piece in line.split(prefix).skip(1) {
+                    let literal = piece.split('"').next().unwrap();
+                    let target = format!("{}{literal}", &prefix[1..]);
+                    let path_only = target.split('?').next().unwrap();
+                    found.push((format!("{}:{}", path.display(), n + 1), path_only.to_string()));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn every_path_the_cli_calls_is_served() {
+    let mut found = Vec::new();
+    cli_paths(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut found);
+    assert!(!found.is_empty());
+    let phantom: Vec<String> = found
+        .iter()
+        .filter(|(_, path)| !served_routes().iter().any(|r| r.matches(path)))
+        .map(|(at, path)| format!("{at}: {path}"))
+        .collect();
+    assert!(phantom.is_empty(), "paths with no server handler:\n{}", phantom.join("\n"));
+}
+
+fn rdv(port: u16) -> Command {
+    let mut cmd = Command::cargo_bin("rdv").unwrap();
+    cmd.env_remove("RDV_API_SOCKET")
+        .env_remove("RDV_SESSION_ID")
+        .env("RDV_CONFIG", "/nonexistent/rdv-config.toml")
+        .env("RDV_API_PORT", port.to_string())
+        .env("RDV_API_KEY", "contract-key");
+    cmd
+}
+
+#[test]
+fn session_list_sends_bearer_and_decodes_sessions() {
+    let (port, server) = stub(vec![(
+        200,
+        r#"{"sessions":[{"id":"s-1","name":"api","status":"active","folderId":"f-1","workingDirectory":"/work/api","terminalType":"agent"}]}"#,
+    )]);
+    rdv(port)
+        .args(["session", "list"])
+        .assert()
+        .success()
+        .stdout(contains("\"workingDirectory\": \"/work/api\""))
+        .stdout(contains("\"terminalType\": \"agent\""));
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["GET /api/sessions"]);
+    assert_eq!(reqs[0].header("authorization"), Some("Bearer contract-key"));
+}
+
+#[test]
+fn send_prompt_waits_for_agent_and_escapes_newlines() {
+    let (port, server) = stub(vec![
+        (200, r#"{"id":"s-1","agentProvider":"claude"}"#),
+        (200, r#"{"content":"Loading..."}"#),
+        (200, r#"{"content":">\n  ? for shortcuts"}"#),
+        (200, r#"{"ok":true}"#),
+    ]);
+    rdv(port)
+        .env_remove("RDV_TERMINAL_SOCKET")
+        .env("RDV_TERMINAL_PORT", port.to_string())
+        .args(["send", "text", "--prompt", "s-1", "fix it\nthen test"])
+        .assert()
+        .success();
+
+    let reqs = server.join().unwrap();
+    assert_requests(
+        &reqs,
+        &[
+            "GET /api/sessions/s-1",
+            "GET /internal/screen?sessionId=s-1",
+            "GET /internal/screen?sessionId=s-1",
+            "POST /internal/pty-write",
+        ],
+    );
+    assert_eq!(reqs[3].json()["text"], "fix it\\\nthen test");
+}
+
+#[test]
+fn requests_carry_trace_headers_and_rdv_trace_logs_timing() {
+    let (port, server) = stub(vec![(200, r#"{"sessions":[]}"#)]);
+    rdv(port)
+        .env("RDV_TRACE", "1")
+        .args(["session", "list"])
+        .assert()
+        .success()
+        .stderr(contains("rdv: GET /api/sessions -> 200 in "));
+
+    let reqs = server.join().unwrap();
+    let request_id = reqs[0].header("x-request-id").expect("x-request-id sent");
+    let traceparent = reqs[0].header("traceparent").expect("traceparent sent");
+    assert!(traceparent.starts_with("00-"));
+    assert!(traceparent.ends_with(&format!("-{request_id}-01")));
+}
+
+#[test]
+fn tmux_hook_reports_rdv_sessions_only() {
+    let (port, server) = stub(vec![(204, "")]);
+    rdv(port)
+        .env("RDV_TERMINAL_PORT", port.to_string())
+        .args(["_hook", "fire", "alert-activity", "rdv-7f0c2a9e-1b2c-4d3e-8f90-123456789abc"])
+        .assert()
+        .success()
+        .stdout("");
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["POST /internal/session-activity?sessionId=7f0c2a9e-1b2c-4d3e-8f90-123456789abc&event=alert-activity"]);
+
+    rdv(1)
+        .env("RDV_TERMINAL_PORT", "1")
+        .args(["_hook", "fire", "pane-died", "scratch"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn json_flag_reports_errors_as_json_on_stderr() {
+    let (port, server) = stub(vec![(404, r#"{"detail":"Session not found","requestId":"req-4"}"#)]);
+    let out = rdv(port)
+        .args(["--json", "session", "close", "s-1"])
+        .assert()
+        .failure()
+        .get_output()
+        .stderr
+        .clone();
+    server.join().unwrap();
+    let err: serde_json::Value = serde_json::from_slice(&out).expect("stderr is JSON");
+    assert_eq!(err["error"]["kind"], "not_found");
+    assert_eq!(err["error"]["requestId"], "req-4");
+
+    rdv(1).args(["--json", "--human", "status"]).assert().failure();
+}
+
+#[test]
+fn peek_filters_the_last_lines() {
+    let (port, server) = stub(vec![(200, r#"{"content":"cargo build\nerror: E0308\nwarning: unused\nerror: E0425\n\n"}"#)]);
+    rdv(port)
+        .env("RDV_TERMINAL_PORT", port.to_string())
+        .args(["--human", "peek", "s-1", "--lines", "3", "--grep", "error"])
+        .assert()
+        .success()
+        .stdout("error: E0308\nerror: E0425\n");
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["GET /internal/screen?sessionId=s-1"]);
+}
+
+#[test]
+fn peek_streams_json_lines_without_human() {
+    let (port, _server) = stub(vec![(200, r#"{"content":"cargo build\nerror: E0308\n"}"#)]);
+    rdv(port)
+        .env("RDV_TERMINAL_PORT", port.to_string())
+        .args(["peek", "s-1", "--grep", "error"])
+        .assert()
+        .success()
+        .stdout("{\"line\":\"error: E0308\",\"sessionId\":\"s-1\"}\n");
+}
+
+#[test]
+fn completions_and_man_need_no_server() {
+    rdv(1)
+        .args(["completions", "zsh"])
+        .assert()
+        .success()
+        .stdout(contains("COMPLETE=\"zsh\""));
+    rdv(1).arg("man").assert().success().stdout(contains(".TH rdv 1"));
+}
+
+#[test]
+fn session_prune_dry_run_selects_locally_and_deletes_nothing() {
+    let (port, server) = stub(vec![(
+        200,
+        r#"{"sessions":[{"id":"s-1","name":"old","status":"suspended","projectId":"f-1","lastActivityAt":"2020-01-01T00:00:00Z"},{"id":"s-2","name":"other","status":"suspended","projectId":"f-2","lastActivityAt":"2020-01-01T00:00:00Z"}]}"#,
+    )]);
+    rdv(port)
+        .args([
+            "--human", "session", "prune", "--folder-id", "f-1", "--status", "suspended", "--idle", "2h", "--dry-run",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Would close 1 session(s)"));
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["GET /api/sessions?status=suspended"]);
+
+    rdv(1).args(["session", "prune"]).assert().failure();
+    rdv(1).args(["session", "prune", "--folder-id", ""]).assert().failure();
+}
+
+#[test]
+fn session_create_waits_for_dependency_output_before_posting() {
+    let (port, server) = stub(vec![
+        (200, r#"{"id":"s-1","status":"active"}"#),
+        (200, r#"{"content":"Compiling...\nTests ready on :3000"}"#),
+        (201, r#"{"id":"s-2","status":"active"}"#),
+    ]);
+    rdv(port)
+        .args(["session", "create", "--name", "e2e", "--depends-on", "s-1", "--ready-output", "Tests? ready"])
+        .env("RDV_TERMINAL_PORT", port.to_string())
+        .assert()
+        .success();
+
+    let reqs = server.join().unwrap();
+    assert_requests(
+        &reqs,
+        &["GET /api/sessions/s-1", "GET /internal/screen?sessionId=s-1", "POST /api/sessions"],
+    );
+    assert_eq!(reqs[2].json(), serde_json::json!({ "name": "e2e" }));
+
+    rdv(1)
+        .args(["session", "create", "--depends-on", "s-1", "--ready-port", "3000", "--ready-output", "x"])
+        .assert()
+        .failure();
+    rdv(1).args(["session", "create", "--depends-on", "s-1"]).assert().failure();
+}
+
+#[test]
+fn github_stats_refresh_posts_to_repo_stats() {
+    let (port, server) = stub(vec![(
+        200,
+        r#"{"repository":{"id":"r-1","fullName":"btli/remote-dev","defaultBranch":"main","stats":{"repositoryId":"r-1","openPRCount":4,"openIssueCount":12,"ciStatus":{"state":"failing","checkRuns":[]},"lastFetchedAt":"2026-01-01T00:00:00Z"}},"refreshedAt":"2026-01-01T00:00:00Z"}"#,
+    )]);
+    rdv(port)
+        .args(["--human", "github", "stats", "r-1", "--refresh"])
+        .assert()
+        .success()
+        .stdout(contains("failing (main)"));
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["POST /api/github/stats/r-1"]);
+}
+
+#[test]
+fn quota_errors_have_their_own_kind() {
+    let (port, _server) = stub(vec![(
+        409,
+        r#"{"error":"Folder already has 5 open sessions (limit 5)","code":"QUOTA_EXCEEDED"}"#,
+    )]);
+    rdv(port)
+        .args(["--json", "session", "create", "--name", "x"])
+        .assert()
+        .failure()
+        .stderr(contains(r#""kind":"quota_exceeded""#));
+}
+
+#[test]
+fn note_add_uses_session_env_and_list_filters_by_folder() {
+    let note = r#"{"note":{"id":"n-1","folderId":"f-1","type":"note","title":"flaky test","content":"flaky test","tags":["ci"]}}"#;
+    let notes = r#"{"notes":[{"id":"n-1","folderId":"f-1","type":"note","title":"flaky test","content":"flaky test","tags":["ci"]}]}"#;
+    let (port, server) = stub(vec![(201, note), (200, notes)]);
+    rdv(port)
+        .env("RDV_SESSION_ID", "s-1")
+        .args(["note", "add", "flaky test", "--tag", "ci"])
+        .assert()
+        .success();
+    rdv(port)
+        .args(["--human", "note", "list", "--folder-id", "f-1", "--tag", "ci"])
+        .assert()
+        .success()
+        .stdout(contains("n-1"));
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["POST /api/notes", "GET /api/notes?folderId=f-1&tag=ci"]);
+    assert_eq!(reqs[0].json()["sessionId"], "s-1");
+    assert_eq!(reqs[0].json()["type"], "note");
+    assert_eq!(reqs[0].json()["tags"], serde_json::json!(["ci"]));
+}
+
+#[test]
+fn config_set_keeps_comments_and_get_reports_source() {
+    let dir = std::env::temp_dir().join(format!("rdv-config-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("config.toml");
+    std::fs::write(&file, "# local dev box\ntrace = false\n").unwrap();
+
+    rdv(1)
+        .env("RDV_CONFIG", &file)
+        .args(["config", "set", "api_port", "7001"])
+        .assert()
+        .success();
+    let written = std::fs::read_to_string(&file).unwrap();
+    assert!(written.starts_with("# local dev box\ntrace = false\n"));
+    assert!(written.contains("api_port = 7001"));
+
+    rdv(1)
+        .env("RDV_CONFIG", &file)
+        .env_remove("RDV_API_PORT")
+        .args(["config", "get", "api_port"])
+        .assert()
+        .success()
+        .stdout(contains("\"source\": \"file\""));
+
+    // rdv ignores a port of 0, so the file is still where the value comes from.
+    rdv(1)
+        .env("RDV_CONFIG", &file)
+        .env("RDV_API_PORT", "0")
+        .args(["config", "get", "api_port"])
+        .assert()
+        .success()
+        .stdout(contains("\"source\": \"file\""));
+
+    // Unknown keys are pointed out by `rdv config`, not by every command.
+    std::fs::write(&file, "api_port = 7001\ncolour = \"auto\"\n").unwrap();
+    rdv(1)
+        .env("RDV_CONFIG", &file)
+        .args(["config", "path"])
+        .assert()
+        .success()
+        .stderr(contains("unknown key").not());
+    rdv(1)
+        .env("RDV_CONFIG", &file)
+        .args(["config", "get"])
+        .assert()
+        .success()
+        .stderr(contains("unknown key `colour`"));
+
+    std::fs::write(&file, "api_port = \"high\"\n").unwrap();
+    rdv(1).env("RDV_CONFIG", &file).args(["config", "validate"]).assert().failure();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn session_create_retries_5xx_with_the_same_idempotency_key() {
+    let (port, server) = stub(vec![
+        (503, r#"{"detail":"restarting"}"#),
+        (201, r#"{"id":"s-1","name":"api"}"#),
+    ]);
+    rdv(port)
+        .args(["session", "create", "--name", "api", "--idempotency-key", "deploy-42"])
+        .assert()
+        .success();
+
+    let reqs = server.join().unwrap();
+    assert_eq!(reqs.len(), 2);
+    assert_eq!(reqs[0].header("idempotency-key"), Some("deploy-42"));
+    assert_eq!(reqs[1].header("idempotency-key"), Some("deploy-42"));
+}
+
+#[test]
+fn task_list_decodes_route_shape_and_claim_patches_status() {
+    let task = r#"{"id":"t-1","userId":"u-1","projectId":null,"sessionId":null,"title":"Fix login","description":null,"status":"in_progress","priority":"high","source":"manual","labels":[],"subtasks":[],"metadata":{},"owner":"7f0c2a9e-1b2c-4d3e-8f90-123456789abc","sortOrder":0,"createdAt":"2026-03-01T12:00:00Z","updatedAt":"2026-03-01T12:00:00Z","blockedBy":["t-0"]}"#;
+    let list = r#"[{"id":"t-1","userId":"u-1","projectId":"f-1","sessionId":null,"title":"Fix login","description":null,"status":"open","priority":"high","source":"manual","labels":[],"subtasks":[],"metadata":{},"owner":null,"sortOrder":0,"createdAt":"2026-03-01T12:00:00Z","updatedAt":"2026-03-01T12:00:00Z","blockedBy":[]},{"id":"t-2","userId":"u-1","projectId":"f-1","sessionId":null,"title":"Docs","description":null,"status":"done","priority":"low","source":"agent","labels":[],"subtasks":[],"metadata":{},"owner":null,"sortOrder":1,"createdAt":"2026-03-01T12:00:00Z","updatedAt":"2026-03-01T12:00:00Z","blockedBy":[]}]"#;
+    let (port, server) = stub(vec![(200, list), (200, task)]);
+    rdv(port)
+        .env("RDV_PROJECT_ID", "f-1")
+        .args(["task", "list", "--status", "open"])
+        .assert()
+        .success()
+        .stdout(contains("t-1"))
+        .stdout(contains("t-2").not());
+    rdv(port)
+        .env("RDV_SESSION_ID", "7f0c2a9e-1b2c-4d3e-8f90-123456789abc")
+        .args(["task", "claim", "t-1"])
+        .assert()
+        .success()
+        .stdout(contains("\"blockedBy\""));
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["GET /api/tasks?folderId=f-1", "PATCH /api/tasks/t-1"]);
+    assert_eq!(reqs[1].json(), serde_json::json!({ "status": "in_progress", "owner": "7f0c2a9e-1b2c-4d3e-8f90-123456789abc" }));
+}
+
+#[test]
+fn usage_lists_limited_claude_profiles() {
+    let (port, server) = stub(vec![(
+        200,
+        r#"{"profiles":[{"id":"p-1","name":"work","accountKind":"subscription","emailAddress":"a@example.com","organizationName":null,"limitState":{"limitStatus":"limited","window5hPct":100,"window7dPct":40,"resetAt5h":1767225600000,"resetAt7d":null,"effectiveResetAt":1767225600000},"pools":[]},{"id":"p-2","name":"spare","accountKind":"api_key","emailAddress":null,"organizationName":null,"limitState":{"limitStatus":"available","window5hPct":null,"window7dPct":null,"resetAt5h":null,"resetAt7d":null,"effectiveResetAt":null},"pools":[]}]}"#,
+    )]);
+    rdv(port)
+        .args(["--human", "usage", "--limited"])
+        .assert()
+        .success()
+        .stdout(contains("a@example.com").and(contains("spare").not()));
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["GET /api/claude/usage"]);
+}
+
+#[test]
+fn status_reports_failed_sections_as_unavailable() {
+    let (port, server) = stub(vec![
+        (404, r#"{"error":"Not found"}"#),
+        (200, r#"{"sessions":[]}"#),
+        (500, r#"{"error":"Internal server error"}"#),
+    ]);
+    let out = rdv(port).arg("status").assert().success().get_output().stdout.clone();
+    let dashboard: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert!(dashboard["usage"]["error"].as_str().unwrap().contains("500"));
+    // A server without the pause route must not read as "not paused".
+    assert!(dashboard["automationPause"]["error"].as_str().unwrap().contains("404"));
+    assert_eq!(server.join().unwrap().len(), 3);
+}
+
+#[test]
+fn project_list_filters_by_group() {
+    let (port, server) = stub(vec![(
+        200,
+        r#"{"projects":[{"id":"p-1","name":"web","groupId":"g-1","isAutoCreated":false}]}"#,
+    )]);
+    rdv(port)
+        .args(["project", "list", "--group-id", "g-1"])
+        .assert()
+        .success()
+        .stdout(contains("\"groupId\": \"g-1\""));
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["GET /api/projects?groupId=g-1"]);
+}
+
+#[test]
+fn project_create_body_matches_route_schema() {
+    let (port, server) = stub(vec![(201, r#"{"project":{"id":"p-2","name":"docs","groupId":"g-1"}}"#)]);
+    rdv(port)
+        .args(["project", "create", "--group-id", "g-1", "--name", "docs"])
+        .assert()
+        .success();
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["POST /api/projects"]);
+    let body = reqs[0].json();
+    assert_eq!(body["groupId"], "g-1");
+    assert_eq!(body["name"], "docs");
+}
+
+#[test]
+fn error_status_and_body_are_surfaced() {
+    let (port, server) = stub(vec![(404, r#"{"error":"Session not found"}"#)]);
+    rdv(port)
+        .args(["session", "close", "missing"])
+        .assert()
+        .failure()
+        .stderr(contains("HTTP 404"))
+        .stderr(contains("Session not found"));
+    server.join().unwrap();
+}
+
+#[test]
+fn rate_limited_request_is_retried() {
+    let (port, server) = stub(vec![
+        (429, r#"{"error":"Too many requests"}"#),
+        (200, r#"{"sessions":[]}"#),
+    ]);
+    rdv(port).args(["session", "list"]).assert().success();
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["GET /api/sessions", "GET /api/sessions"]);
+}
+
+#[test]
+fn session_list_follows_next_cursor() {
+    let (port, server) = stub(vec![
+        (200, r#"{"sessions":[{"id":"s-1"}],"nextCursor":"c-2"}"#),
+        (200, r#"{"sessions":[{"id":"s-2"}]}"#),
+    ]);
+    rdv(port)
+        .args(["session", "list"])
+        .assert()
+        .success()
+        .stdout(contains("s-1"))
+        .stdout(contains("s-2"));
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["GET /api/sessions", "GET /api/sessions?cursor=c-2"]);
+}
+
+#[test]
+fn session_list_stops_on_a_cursor_cycle() {
+    let (port, server) = stub(vec![
+        (200, r#"{"sessions":[{"id":"s-1"}],"nextCursor":"c-2"}"#),
+        (200, r#"{"sessions":[{"id":"s-2"}],"nextCursor":"c-3"}"#),
+        (200, r#"{"sessions":[{"id":"s-3"}],"nextCursor":"c-2"}"#),
+    ]);
+    rdv(port)
+        .args(["session", "list"])
+        .assert()
+        .failure()
+        .stderr(contains("repeated page cursor"));
+
+    let reqs = server.join().unwrap();
+    assert_requests(
+        &reqs,
+        &["GET /api/sessions", "GET /api/sessions?cursor=c-2", "GET /api/sessions?cursor=c-3"],
+    );
+}
+
+#[test]
+fn offline_note_is_queued_and_replayed_by_flush() {
+    let data = std::env::temp_dir().join(format!("rdv-contract-queue-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&data);
+
+    // Nothing listens on port 1, so the note is journaled instead of failing.
+    rdv(1)
+        .env("RDV_OFFLINE_QUEUE", "1")
+        .env("RDV_DATA_DIR", &data)
+        .env("RDV_SESSION_ID", "s-1")
+        .args(["note", "add", "seen again"])
+        .assert()
+        .success()
+        .stdout(contains("\"queued\": true"));
+    assert!(data.join("rdv/queue.jsonl").exists());
+
+    let (port, server) = stub(vec![(201, r#"{"note":{"id":"n-1"}}"#)]);
+    rdv(port)
+        .env("RDV_DATA_DIR", &data)
+        .args(["doctor", "--flush-queue"])
+        .assert()
+        .success()
+        .stdout(contains("\"replayed\": 1"));
+
+    let reqs = server.join().unwrap();
+    assert_requests(&reqs, &["POST /api/notes"]);
+    assert_eq!(reqs[0].json()["content"], "seen again");
+    assert!(!data.join("rdv/queue.jsonl").exists());
+    std::fs::remove_dir_all(&data).ok();
+}
@@ -0,0 +1,70 @@
+use std::path::{Path, PathBuf};
+
+use assert_cmd::Command;
+
+/// A fresh directory to stand in for the user's home, so doctor never reads
+/// or writes the real `~/.remote-dev`, settings file or offline queue.
+fn scratch_home(test: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("rdv-doctor-{test}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    root
+}
+
+fn rdv(home: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("rdv").unwrap();
+    cmd.env_remove("RDV_API_SOCKET")
+        .env_remove("RDV_TERMINAL_SOCKET")
+        .env_remove("RDV_CONFIG")
+        .env_remove("RDV_DATA_DIR")
+        .env_remove("RDV_API_KEY")
+        .env_remove("RDV_SESSION_ID")
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("XDG_DATA_HOME", home.join(".local/share"))
+        .env("XDG_STATE_HOME", home.join(".local/state"))
+        .env("XDG_CACHE_HOME", home.join(".cache"))
+        .env("XDG_RUNTIME_DIR", home.join("run"))
+        .env("RDV_API_PORT", "1");
+    cmd
+}
+
+#[test]
+fn doctor_reports_unreachable_server() {
+    let home = scratch_home("unreachable");
+    rdv(&home)
+        .args(["doctor"])
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("unreachable"));
+    std::fs::remove_dir_all(home).ok();
+}
+
+#[test]
+fn doctor_backup_and_restore_round_trip() {
+    let root = scratch_home("backup");
+    let data = root.join("data");
+    std::fs::create_dir_all(data.join("profiles")).unwrap();
+    std::fs::write(data.join("profiles/CLAUDE.md"), "original").unwrap();
+    let archive = root.join("snapshot.tar.gz");
+
+    let rdv = || {
+        let mut cmd = rdv(&root);
+        cmd.env("RDV_DATA_DIR", &data);
+        cmd
+    };
+    rdv().args(["doctor", "backup"]).arg(&archive).assert().success();
+
+    std::fs::write(data.join("profiles/CLAUDE.md"), "edited").unwrap();
+    rdv().args(["doctor", "restore"]).arg(&archive).assert().success();
+
+    assert_eq!(std::fs::read_to_string(data.join("profiles/CLAUDE.md")).unwrap(), "original");
+    let kept: Vec<_> = std::fs::read_dir(&data)
+        .unwrap()
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with("profiles.pre-restore-"))
+        .collect();
+    assert_eq!(kept.len(), 1);
+    assert_eq!(std::fs::read_to_string(kept[0].path().join("CLAUDE.md")).unwrap(), "edited");
+    std::fs::remove_dir_all(root).ok();
+}
@@ -0,0 +1,21 @@
+use assert_cmd::Command;
+
+#[test]
+fn pause_help_shows_flags() {
+    let mut cmd = Command::cargo_bin("rdv").unwrap();
+    cmd.args(["pause", "--help"]);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("--folder"))
+        .stdout(predicates::str::contains("--for"))
+        .stdout(predicates::str::contains("--reason"));
+}
+
+#[test]
+fn pause_rejects_bad_duration() {
+    let mut cmd = Command::cargo_bin("rdv").unwrap();
+    cmd.env("RDV_API_PORT", "1").args(["pause", "--for", "2weeks"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid duration"));
+}
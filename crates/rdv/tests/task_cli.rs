@@ -0,0 +1,17 @@
+use assert_cmd::Command;
+
+#[test]
+fn task_create_rejects_unknown_priority() {
+    let mut cmd = Command::cargo_bin("rdv").unwrap();
+    cmd.args(["task", "create", "fix login", "--folder-id", "f-1", "--priority", "p0"]);
+    cmd.assert().failure();
+}
+
+#[test]
+fn task_list_requires_folder() {
+    let mut cmd = Command::cargo_bin("rdv").unwrap();
+    cmd.env_remove("RDV_PROJECT_ID").env("RDV_API_PORT", "1").args(["task", "list"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicates::str::contains("RDV_PROJECT_ID"));
+}
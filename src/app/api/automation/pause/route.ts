@@ -0,0 +1,69 @@
+import { NextResponse } from "next/server";
+import { withApiAuth, errorResponse, parseJsonBody } from "@/lib/api";
+import { ProjectService } from "@/services/project-service";
+import * as AutomationPauseService from "@/services/automation-pause-service";
+
+/**
+ * GET /api/automation/pause - Pauses currently in effect
+ *
+ * Returns { global, folders }: the workspace-wide pause (or null) and each
+ * paused folder.
+ */
+export const GET = withApiAuth(async (_request, { userId }) => {
+  return NextResponse.json(AutomationPauseService.getPauseState(userId));
+});
+
+/**
+ * POST /api/automation/pause - Pause automation
+ *
+ * Body: folderId (omit to pause everything), until (ISO timestamp), reason.
+ */
+export const POST = withApiAuth(async (request, { userId }) => {
+  const result = await parseJsonBody<{
+    folderId?: string | null;
+    until?: string | null;
+    reason?: string | null;
+  }>(request);
+  if ("error" in result) return result.error;
+  const { folderId, until, reason } = result.data;
+
+  if (until && Number.isNaN(new Date(until).getTime())) {
+    return errorResponse("until must be an ISO timestamp", 400);
+  }
+
+  let folderName: string | null = null;
+  if (folderId) {
+    const project = await ProjectService.get(folderId);
+    if (!project || project.userId !== userId) {
+      return errorResponse("Folder not found", 404);
+    }
+    folderName = project.name;
+  }
+
+  const pause = AutomationPauseService.pause(userId, { folderId, folderName, until, reason });
+  return NextResponse.json({ pause }, { status: 201 });
+});
+
+/**
+ * DELETE /api/automation/pause - Resume automation
+ *
+ * Optional body: folderId to resume one folder; otherwise lifts the
+ * workspace-wide pause.
+ */
+export const DELETE = withApiAuth(async (request, { userId }) => {
+  // Body is optional (empty body resumes everything); only a malformed
+  // non-empty body is rejected.
+  let folderId: string | null = null;
+  const rawBody = await request.text();
+  if (rawBody.trim()) {
+    try {
+      const parsed = JSON.parse(rawBody) as { folderId?: string | null };
+      folderId = parsed.folderId ?? null;
+    } catch {
+      return errorResponse("Invalid JSON in request body", 400, "INVALID_JSON");
+    }
+  }
+
+  const resumed = AutomationPauseService.resume(userId, folderId);
+  return NextResponse.json({ success: true, resumed });
+});
@@ -1,73 +1,51 @@
 /**
  * GET /api/github/stats/:repoId - Get stats for a specific repository
  * POST /api/github/stats/:repoId - Refresh stats for a specific repository
+ *
+ * Authentication: Supports both session auth and API key auth (Bearer token).
  */
 
 import { NextResponse } from "next/server";
-import { auth } from "@/auth";
+import { withApiAuth, errorResponse } from "@/lib/api";
 import * as GitHubStatsService from "@/services/github-stats-service";
 import { createLogger } from "@/lib/logger";
 
 const log = createLogger("api/github");
 
-interface RouteParams {
-  params: Promise<{ repoId: string }>;
-}
-
-export async function GET(_request: Request, { params }: RouteParams) {
+export const GET = withApiAuth(async (_request, { userId, params }) => {
   try {
-    const session = await auth();
-    if (!session?.user?.id) {
-      return NextResponse.json(
-        { error: "Unauthorized", code: "UNAUTHORIZED" },
-        { status: 401 }
-      );
+    const repoId = params?.repoId;
+    if (!repoId) {
+      return errorResponse("Repository ID is required", 400, "ID_REQUIRED");
     }
 
-    const { repoId } = await params;
-
-    const repositories = await GitHubStatsService.getEnrichedRepositories(
-      session.user.id
-    );
+    const repositories = await GitHubStatsService.getEnrichedRepositories(userId);
 
     const repository = repositories.find((r) => r.id === repoId);
 
     if (!repository) {
-      return NextResponse.json(
-        { error: "Repository not found", code: "NOT_FOUND" },
-        { status: 404 }
-      );
+      return errorResponse("Repository not found", 404, "NOT_FOUND");
     }
 
     return NextResponse.json({ repository });
   } catch (error) {
     log.error("Error fetching repository stats", { error: String(error) });
     const err = error as Error;
-    return NextResponse.json(
-      { error: err.message, code: "FETCH_ERROR" },
-      { status: 500 }
-    );
+    return errorResponse(err.message, 500, "FETCH_ERROR");
   }
-}
+});
 
-export async function POST(_request: Request, { params }: RouteParams) {
+export const POST = withApiAuth(async (_request, { userId, params }) => {
   try {
-    const session = await auth();
-    if (!session?.user?.id) {
-      return NextResponse.json(
-        { error: "Unauthorized", code: "UNAUTHORIZED" },
-        { status: 401 }
-      );
+    const repoId = params?.repoId;
+    if (!repoId) {
+      return errorResponse("Repository ID is required", 400, "ID_REQUIRED");
     }
 
-    const { repoId } = await params;
-
-    await GitHubStatsService.refreshRepositoryStats(session.user.id, repoId);
+    await GitHubStatsService.refreshRepositoryStats(userId, repoId);
 
     // Get updated repository
-    const repositories = await GitHubStatsService.getEnrichedRepositories(
-      session.user.id
-    );
+    const repositories = await GitHubStatsService.getEnrichedRepositories(userId);
 
     const repository = repositories.find((r) => r.id === repoId);
 
@@ -78,9 +56,6 @@ export async function POST(_request: Request, { params }: RouteParams) {
   } catch (error) {
     log.error("Error refreshing repository stats", { error: String(error) });
     const err = error as Error;
-    return NextResponse.json(
-      { error: err.message, code: "REFRESH_ERROR" },
-      { status: 500 }
-    );
+    return errorResponse(err.message, 500, "REFRESH_ERROR");
   }
-}
+});
@@ -14,10 +14,12 @@ const log = createLogger("api/github");
  * - projectPath: Uses the path directly if it's a valid git repo (preferred)
  *
  * When projectPath is provided, worktrees are created relative to that path.
+ * With createNewBranch, `reuseExisting` returns a clean worktree already on the
+ * branch instead of creating a suffixed one (`reused: true` in the response).
  */
 export const POST = withAuth(async (request, { userId }) => {
   const body = await request.json();
-  const { repositoryId, projectPath, branch, createNewBranch, baseBranch } = body;
+  const { repositoryId, projectPath, branch, createNewBranch, baseBranch, reuseExisting } = body;
 
   if (!branch) {
     return errorResponse("branch is required", 400);
@@ -67,15 +69,21 @@ export const POST = withAuth(async (request, { userId }) => {
 
   try {
     let worktreePath: string;
+    let createdBranch = branch;
+    let reused = false;
 
     if (createNewBranch) {
       // Create a new branch with a worktree
       const result = await WorktreeService.createBranchWithWorktree(
         repoPath,
         branch,
-        baseBranch || defaultBranch
+        baseBranch || defaultBranch,
+        undefined,
+        { reuseExisting: reuseExisting === true }
       );
       worktreePath = result.worktreePath;
+      createdBranch = result.branch;
+      reused = result.reused;
     } else {
       // Create worktree for existing branch
       worktreePath = await WorktreeService.createWorktree(
@@ -87,7 +95,8 @@ export const POST = withAuth(async (request, { userId }) => {
     return NextResponse.json({
       success: true,
       worktreePath,
-      branch,
+      branch: createdBranch,
+      reused,
     });
   } catch (error) {
     if (error instanceof WorktreeService.WorktreeServiceError) {
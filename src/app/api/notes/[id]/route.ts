@@ -0,0 +1,40 @@
+import { NextResponse } from "next/server";
+import { withApiAuth, errorResponse, parseJsonBody } from "@/lib/api";
+import { getNote, updateNote, deleteNote } from "@/services/note-service";
+import type { UpdateNoteInput } from "@/services/note-service";
+
+export const GET = withApiAuth(async (_request, { userId, params }) => {
+  const id = params?.id;
+  if (!id) return errorResponse("Note ID is required", 400);
+
+  const note = await getNote(id, userId);
+  if (!note) return errorResponse("Note not found", 404);
+
+  return NextResponse.json({ note });
+});
+
+export const PATCH = withApiAuth(async (request, { userId, params }) => {
+  const id = params?.id;
+  if (!id) return errorResponse("Note ID is required", 400);
+
+  const result = await parseJsonBody<UpdateNoteInput>(request);
+  if ("error" in result) return result.error;
+  if (result.data.tags !== undefined && !Array.isArray(result.data.tags)) {
+    return errorResponse("tags must be an array of strings", 400);
+  }
+
+  const note = await updateNote(id, userId, result.data);
+  if (!note) return errorResponse("Note not found", 404);
+
+  return NextResponse.json({ note });
+});
+
+export const DELETE = withApiAuth(async (_request, { userId, params }) => {
+  const id = params?.id;
+  if (!id) return errorResponse("Note ID is required", 400);
+
+  const deleted = await deleteNote(id, userId);
+  if (!deleted) return errorResponse("Note not found", 404);
+
+  return new NextResponse(null, { status: 204 });
+});
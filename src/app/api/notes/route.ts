@@ -0,0 +1,49 @@
+import { NextResponse } from "next/server";
+import { withApiAuth, errorResponse, parseJsonBody } from "@/lib/api";
+import { listNotes, createNote, NOTE_TYPES } from "@/services/note-service";
+import type { CreateNoteInput } from "@/services/note-service";
+
+/**
+ * GET /api/notes - List notes, newest first
+ *
+ * Query: folderId, tag, q (case-insensitive text match), limit.
+ */
+export const GET = withApiAuth(async (request, { userId }) => {
+  const url = new URL(request.url);
+  const folderId = url.searchParams.get("folderId") ?? undefined;
+  const tag = url.searchParams.get("tag") ?? undefined;
+  const query = url.searchParams.get("q") ?? undefined;
+  const limitParam = url.searchParams.get("limit");
+  const limit = limitParam ? parseInt(limitParam, 10) : undefined;
+  if (limit !== undefined && (!Number.isFinite(limit) || limit < 1)) {
+    return errorResponse("limit must be a positive integer", 400);
+  }
+
+  const notes = await listNotes(userId, { folderId, tag, query, limit });
+  return NextResponse.json({ notes });
+});
+
+/**
+ * POST /api/notes - Capture a note
+ *
+ * Body: content (required), title, type, tags, folderId, sessionId. Without
+ * folderId the note is filed under the session's folder.
+ */
+export const POST = withApiAuth(async (request, { userId }) => {
+  const result = await parseJsonBody<CreateNoteInput>(request);
+  if ("error" in result) return result.error;
+  const body = result.data;
+
+  if (!body.content?.trim()) {
+    return errorResponse("Note content is required", 400);
+  }
+  if (body.type && !NOTE_TYPES.includes(body.type)) {
+    return errorResponse(`type must be one of ${NOTE_TYPES.join(", ")}`, 400);
+  }
+  if (body.tags !== undefined && !Array.isArray(body.tags)) {
+    return errorResponse("tags must be an array of strings", 400);
+  }
+
+  const note = await createNote(userId, body);
+  return NextResponse.json({ note }, { status: 201 });
+});
@@ -107,6 +107,11 @@ export const POST = withApiAuth(async (request, { userId }) => {
       return errorResponse("Invalid file path", 400, "INVALID_FILE_PATH");
     }
 
+    // A client's Idempotency-Key rides the scope-key dedup below, so a retried
+    // create (rdv retries timeouts and 5xx) returns the first session instead
+    // of a second one. Namespaced so it can't match a UI tab's scope key.
+    const idempotencyKey = request.headers.get("idempotency-key");
+
     const input: CreateSessionInput = {
       name: body.name || "Terminal",
       projectPath: validatedPath,
@@ -130,7 +135,8 @@ export const POST = withApiAuth(async (request, { userId }) => {
       baseBranch: body.baseBranch,
       worktreeType: body.worktreeType as CreateSessionInput["worktreeType"],
       // Plugin-level dedup + generic metadata passthrough
-      scopeKey: body.scopeKey ?? null,
+      scopeKey:
+        body.scopeKey ?? (idempotencyKey ? `idempotency:${idempotencyKey}` : null),
       typeMetadata: body.typeMetadata,
       // SSH session
       sshConnectionId: body.sshConnectionId,
@@ -153,7 +159,8 @@ export const POST = withApiAuth(async (request, { userId }) => {
     log.error("Error creating session", { error: String(error) });
 
     if (error instanceof SessionService.SessionServiceError) {
-      return errorResponse(error.message, 400, error.code);
+      const status = error.code === "QUOTA_EXCEEDED" ? 409 : 400;
+      return errorResponse(error.message, status, error.code);
     }
 
     return errorResponse("Failed to create session", 500);
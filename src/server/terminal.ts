@@ -1129,6 +1129,43 @@ async function handleInternalApi(req: IncomingMessage, res: ServerResponse): Pro
     return true;
   }
 
+  // Handle tmux hook callbacks installed by `rdv _hook install`, so activity
+  // and pane exits land as they happen instead of on the next poll or sweep.
+  // POST /internal/session-activity?sessionId=xxx&event=alert-activity|pane-died|client-detached
+  if (pathname === "/internal/session-activity" && req.method === "POST") {
+    const sessionId = query.sessionId as string;
+    const event = query.event as string;
+
+    if (!sessionId || !["alert-activity", "pane-died", "client-detached"].includes(event)) {
+      sendJson(res, 400, { error: "Missing sessionId or unknown event" });
+      return true;
+    }
+
+    try {
+      if (event === "pane-died") {
+        // Same transition the 30s liveness sweep makes, just sooner.
+        const { reconcileLiveness } = await import("@/services/session-liveness-service");
+        await reconcileLiveness();
+      } else {
+        const { db } = await import("@/db");
+        const { terminalSessions } = await import("@/db/schema");
+        const { eq } = await import("drizzle-orm");
+        const now = new Date();
+        await db
+          .update(terminalSessions)
+          .set({ lastActivityAt: now, updatedAt: now })
+          .where(eq(terminalSessions.id, sessionId));
+      }
+    } catch (err) {
+      internalLog.error("Session activity update failed", { sessionId, event, error: String(err) });
+      sendJson(res, 500, { error: "Failed to record session activity" });
+      return true;
+    }
+
+    sendJson(res, 200, { success: true, sessionId, event });
+    return true;
+  }
+
   // Handle agent activity status from Claude Code hooks
   // Called by hooks: POST /internal/agent-status?sessionId=xxx&status=running|waiting
   //   [&source=subagent-stop]
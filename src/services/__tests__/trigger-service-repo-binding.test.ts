@@ -128,6 +128,7 @@ vi.mock("../agent-run-service", () => ({
 }));
 vi.mock("../session-service", () => ({ createSessionWithDedupFlag: vi.fn() }));
 vi.mock("../tmux-service", () => ({ sendKeys: vi.fn(), capturePane: vi.fn() }));
+vi.mock("../automation-pause-service", () => ({ activePause: vi.fn(() => null) }));
 
 import { handleEvent } from "../trigger-service";
 import type { GithubEvent } from "@/lib/github-webhook-auth";
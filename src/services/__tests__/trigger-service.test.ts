@@ -82,6 +82,7 @@ function makeDeps(over: Partial<TriggerDeps> = {}): {
       records.push({ configId, matched, runId });
     }),
     recordDelivery: vi.fn(async () => true),
+    activePause: vi.fn(() => null),
     ...over,
   };
   return {
@@ -158,6 +159,24 @@ describe("TriggerService.handleEvent", () => {
     ]);
   });
 
+  it("records a match but launches nothing while the folder is paused", async () => {
+    const h = makeDeps({
+      activePause: vi.fn(() => ({
+        folderId: "p1",
+        folderName: null,
+        pausedAt: "2026-03-01T00:00:00.000Z",
+        until: null,
+        reason: "deploy freeze",
+      })),
+    });
+    await handleEvent(prLabeled, h.deps);
+    expect(h.deps.activePause).toHaveBeenCalledWith("u1", "p1");
+    expect(h.launches).toBe(0);
+    expect(h.records).toEqual([
+      { configId: "cfg-1", matched: true, runId: null },
+    ]);
+  });
+
   it("treats a duplicate (unique-index) delivery as a no-op second run", async () => {
     const h = makeDeps({
       launchAgentRun: vi.fn(async () => {
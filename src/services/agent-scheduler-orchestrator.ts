@@ -13,6 +13,7 @@ import { Cron } from "croner";
 import { createLogger } from "@/lib/logger";
 import * as AgentScheduleService from "./agent-schedule-service";
 import * as AgentRunService from "./agent-run-service";
+import * as AutomationPauseService from "./automation-pause-service";
 import type { AgentScheduleRow } from "./agent-schedule-service";
 
 const log = createLogger("AgentScheduler");
@@ -172,6 +173,16 @@ class AgentSchedulerOrchestrator {
 
     log.info("Executing agent schedule", { scheduleId });
     try {
+      const pause = AutomationPauseService.activePause(schedule.userId, schedule.projectId);
+      if (pause) {
+        log.info("Automation paused; skipping agent schedule", {
+          scheduleId,
+          folderId: pause.folderId,
+          until: pause.until,
+          reason: pause.reason,
+        });
+        return;
+      }
       await AgentRunService.launchAgentRun({
         userId: schedule.userId,
         projectId: schedule.projectId,
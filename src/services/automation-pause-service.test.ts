@@ -0,0 +1,63 @@
+// @vitest-environment node
+import { describe, it, expect, beforeEach, afterEach } from "vitest";
+import { mkdtempSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { activePause, getPauseState, pause, resume } from "./automation-pause-service";
+
+let tmpDir: string;
+const previousDataDir = process.env.RDV_DATA_DIR;
+
+beforeEach(() => {
+  tmpDir = mkdtempSync(join(tmpdir(), "automation-pause-"));
+  process.env.RDV_DATA_DIR = tmpDir;
+});
+
+afterEach(() => {
+  if (previousDataDir === undefined) delete process.env.RDV_DATA_DIR;
+  else process.env.RDV_DATA_DIR = previousDataDir;
+  rmSync(tmpDir, { recursive: true, force: true });
+});
+
+describe("AutomationPauseService", () => {
+  it("reports nothing paused before any pause is written", () => {
+    expect(getPauseState("u1")).toEqual({ global: null, folders: [] });
+    expect(activePause("u1", "proj-1")).toBeNull();
+  });
+
+  it("blocks every folder while the workspace is paused", () => {
+    pause("u1", { reason: "deploy freeze" });
+
+    expect(activePause("u1")?.reason).toBe("deploy freeze");
+    expect(activePause("u1", "proj-1")?.folderId).toBeNull();
+    expect(activePause("u2")).toBeNull();
+
+    expect(resume("u1")).toBe(true);
+    expect(activePause("u1", "proj-1")).toBeNull();
+  });
+
+  it("scopes a folder pause to that folder", () => {
+    pause("u1", { folderId: "proj-1", folderName: "api" });
+
+    expect(activePause("u1", "proj-1")?.folderName).toBe("api");
+    expect(activePause("u1", "proj-2")).toBeNull();
+    expect(activePause("u1")).toBeNull();
+
+    expect(resume("u1", "proj-2")).toBe(false);
+    expect(resume("u1", "proj-1")).toBe(true);
+    expect(getPauseState("u1").folders).toEqual([]);
+  });
+
+  it("ignores a pause whose until has passed", () => {
+    const now = new Date("2026-03-01T12:00:00Z");
+    pause("u1", { until: "2026-03-01T13:00:00Z" }, now);
+
+    expect(activePause("u1", null, new Date("2026-03-01T12:30:00Z"))).not.toBeNull();
+    expect(activePause("u1", null, new Date("2026-03-01T13:00:01Z"))).toBeNull();
+  });
+
+  it("fails closed on an unreadable state file", () => {
+    writeFileSync(join(tmpDir, "automation-pause.json"), "{not json");
+    expect(() => activePause("u1")).toThrow(/Unreadable automation pause file/);
+  });
+});
@@ -0,0 +1,144 @@
+/**
+ * AutomationPauseService - Workspace- and folder-level kill switch for
+ * automation (`rdv pause` / `rdv resume`).
+ *
+ * While paused, the session scheduler, agent scheduler and GitHub trigger
+ * dispatch skip their work and log why. A pause may carry an `until`
+ * timestamp; once it passes the pause no longer applies and is dropped on the
+ * next write.
+ *
+ * State lives in `${RDV_DATA_DIR}/automation-pause.json`, keyed by user, so a
+ * pause survives a server restart without a schema change.
+ */
+import { existsSync, mkdirSync, readFileSync, renameSync, writeFileSync } from "node:fs";
+import { runtimeDirname as dirname, runtimeJoin as join } from "@/lib/dynamic-fs";
+import { getDataDir } from "@/lib/paths";
+import { createLogger } from "@/lib/logger";
+
+const log = createLogger("AutomationPause");
+
+export interface Pause {
+  /** null for the workspace-wide pause. */
+  folderId: string | null;
+  folderName: string | null;
+  pausedAt: string;
+  /** ISO timestamp the pause lapses at; null means until resumed. */
+  until: string | null;
+  reason: string | null;
+}
+
+export interface PauseState {
+  global: Pause | null;
+  folders: Pause[];
+}
+
+export interface PauseInput {
+  folderId?: string | null;
+  folderName?: string | null;
+  until?: string | null;
+  reason?: string | null;
+}
+
+type PauseFile = Record<string, PauseState>;
+
+function pauseFilePath(): string {
+  return join(getDataDir(), "automation-pause.json");
+}
+
+function readFile(): PauseFile {
+  const path = pauseFilePath();
+  if (!existsSync(path)) return {};
+  try {
+    return JSON.parse(readFileSync(path, "utf8")) as PauseFile;
+  } catch (error) {
+    // Fail closed: an unreadable file must not silently resume automation.
+    throw new Error(`Unreadable automation pause file ${path}: ${String(error)}`);
+  }
+}
+
+function writeFile(data: PauseFile): void {
+  const path = pauseFilePath();
+  mkdirSync(dirname(path), { recursive: true });
+  const tmp = `${path}.tmp`;
+  writeFileSync(tmp, JSON.stringify(data, null, 2), { mode: 0o600 });
+  renameSync(tmp, path);
+}
+
+function isActive(pause: Pause, now: Date): boolean {
+  return !pause.until || new Date(pause.until).getTime() > now.getTime();
+}
+
+function activeState(state: PauseState | undefined, now: Date): PauseState {
+  const global = state?.global && isActive(state.global, now) ? state.global : null;
+  const folders = (state?.folders ?? []).filter((p) => isActive(p, now));
+  return { global, folders };
+}
+
+/**
+ * The user's pauses that are still in effect.
+ */
+export function getPauseState(userId: string, now = new Date()): PauseState {
+  return activeState(readFile()[userId], now);
+}
+
+/**
+ * Pause all automation, or one folder's when folderId is given. Pausing an
+ * already-paused scope replaces its until/reason.
+ */
+export function pause(userId: string, input: PauseInput, now = new Date()): Pause {
+  const data = readFile();
+  const state = activeState(data[userId], now);
+  const entry: Pause = {
+    folderId: input.folderId ?? null,
+    folderName: input.folderName ?? null,
+    pausedAt: now.toISOString(),
+    until: input.until ?? null,
+    reason: input.reason ?? null,
+  };
+  if (entry.folderId) {
+    state.folders = [...state.folders.filter((p) => p.folderId !== entry.folderId), entry];
+  } else {
+    state.global = entry;
+  }
+  data[userId] = state;
+  writeFile(data);
+  log.info("Automation paused", { userId, folderId: entry.folderId, until: entry.until, reason: entry.reason });
+  return entry;
+}
+
+/**
+ * Lift the workspace-wide pause, or one folder's. Returns false when that
+ * scope wasn't paused.
+ */
+export function resume(userId: string, folderId?: string | null, now = new Date()): boolean {
+  const data = readFile();
+  const state = activeState(data[userId], now);
+  let removed: boolean;
+  if (folderId) {
+    const before = state.folders.length;
+    state.folders = state.folders.filter((p) => p.folderId !== folderId);
+    removed = state.folders.length < before;
+  } else {
+    removed = state.global !== null;
+    state.global = null;
+  }
+  data[userId] = state;
+  writeFile(data);
+  log.info("Automation resumed", { userId, folderId: folderId ?? null });
+  return removed;
+}
+
+/**
+ * The pause that blocks automation for this user and folder, if any. The
+ * workspace-wide pause wins over a folder's.
+ */
+export function activePause(
+  userId: string,
+  folderId?: string | null,
+  now = new Date()
+): Pause | null {
+  const state = getPauseState(userId, now);
+  if (state.global) return state.global;
+  if (!folderId) return null;
+  return state.folders.find((p) => p.folderId === folderId) ?? null;
+}
@@ -0,0 +1,109 @@
+// @vitest-environment node
+import { describe, it, expect, beforeEach, afterEach, vi } from "vitest";
+import { createClient, type Client } from "@libsql/client/node";
+import { drizzle } from "drizzle-orm/libsql";
+import { mkdtempSync, rmSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import * as schema from "@/db/schema";
+
+let client: Client;
+let testDb: ReturnType<typeof drizzle<typeof schema>>;
+let tmpDir: string;
+
+vi.mock("@/db", () => ({
+  get db() {
+    return testDb;
+  },
+}));
+
+vi.mock("./task-service", () => ({
+  getSessionContext: vi.fn(async (sessionId: string) =>
+    sessionId === "s-1" ? { id: "s-1", userId: "u1", projectId: "proj-1" } : null
+  ),
+}));
+
+const DDL = [
+  `CREATE TABLE session_memory (
+    id TEXT PRIMARY KEY NOT NULL,
+    user_id TEXT NOT NULL,
+    project_id TEXT,
+    type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    tags TEXT NOT NULL DEFAULT '[]',
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+  );`,
+];
+
+async function resetDb(): Promise<void> {
+  tmpDir = mkdtempSync(join(tmpdir(), "rdv-note-test-"));
+  client = createClient({ url: `file:${join(tmpDir, "test.db")}` });
+  testDb = drizzle(client, { schema });
+  for (const stmt of DDL) await client.execute(stmt);
+}
+
+function cleanupDb(): void {
+  client?.close();
+  if (tmpDir) rmSync(tmpDir, { recursive: true, force: true });
+}
+
+import { createNote, listNotes, getNote, updateNote, deleteNote } from "./note-service";
+
+describe("NoteService", () => {
+  beforeEach(async () => {
+    await resetDb();
+  });
+  afterEach(() => {
+    cleanupDb();
+    vi.clearAllMocks();
+  });
+
+  it("files a note under its session's folder and titles it from the content", async () => {
+    const note = await createNote("u1", {
+      content: "Cache keys include the locale\nso flush per locale",
+      tags: ["cache"],
+      sessionId: "s-1",
+    });
+
+    expect(note.folderId).toBe("proj-1");
+    expect(note.type).toBe("note");
+    expect(note.title).toBe("Cache keys include the locale");
+    expect(await getNote(note.id, "u1")).toEqual(note);
+  });
+
+  it("ignores a session the user doesn't own", async () => {
+    const note = await createNote("u2", { content: "x", sessionId: "s-1" });
+    expect(note.folderId).toBeNull();
+  });
+
+  it("filters by folder, tag and case-insensitive text", async () => {
+    await createNote("u1", { content: "Flaky login test", tags: ["ci"], folderId: "proj-1" });
+    await createNote("u1", { content: "Deploy checklist", tags: ["ops"], folderId: "proj-1" });
+    await createNote("u1", { content: "Other folder login", folderId: "proj-2" });
+    await createNote("u2", { content: "Someone else's login", folderId: "proj-1" });
+
+    const inFolder = await listNotes("u1", { folderId: "proj-1" });
+    expect(inFolder).toHaveLength(2);
+
+    const tagged = await listNotes("u1", { folderId: "proj-1", tag: "ci" });
+    expect(tagged.map((n) => n.content)).toEqual(["Flaky login test"]);
+
+    const searched = await listNotes("u1", { query: "LOGIN" });
+    expect(searched.map((n) => n.content).sort()).toEqual(["Flaky login test", "Other folder login"]);
+  });
+
+  it("updates and deletes only the owner's notes", async () => {
+    const note = await createNote("u1", { content: "draft" });
+
+    expect(await updateNote(note.id, "u2", { content: "hijack" })).toBeNull();
+    const updated = await updateNote(note.id, "u1", { content: "final", tags: ["done"] });
+    expect(updated?.content).toBe("final");
+    expect(updated?.tags).toEqual(["done"]);
+
+    expect(await deleteNote(note.id, "u2")).toBe(false);
+    expect(await deleteNote(note.id, "u1")).toBe(true);
+    expect(await getNote(note.id, "u1")).toBeNull();
+  });
+});
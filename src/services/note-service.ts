@@ -0,0 +1,191 @@
+/**
+ * NoteService - Notes captured by agents and users, stored in session_memory.
+ *
+ * A note belongs to a folder (project); capturing one from a session files it
+ * under that session's folder. Tag and text filtering happen in memory after
+ * the folder-scoped query, which keeps the matching identical on SQLite and
+ * Postgres.
+ */
+import { db } from "@/db";
+import { sessionMemory } from "@/db/schema";
+import { eq, and, desc, isNull } from "drizzle-orm";
+import { safeJsonParse } from "@/lib/utils";
+import { getSessionContext } from "./task-service";
+
+export type NoteType = "note" | "artifact" | "summary";
+
+export const NOTE_TYPES: readonly NoteType[] = ["note", "artifact", "summary"];
+
+export interface Note {
+  id: string;
+  folderId: string | null;
+  type: NoteType;
+  title: string;
+  content: string;
+  tags: string[];
+  createdAt: Date;
+  updatedAt: Date;
+}
+
+export interface CreateNoteInput {
+  content: string;
+  title?: string | null;
+  type?: NoteType;
+  tags?: string[];
+  folderId?: string | null;
+  /** Files the note under this session's folder when folderId is absent. */
+  sessionId?: string | null;
+}
+
+export interface UpdateNoteInput {
+  title?: string;
+  content?: string;
+  tags?: string[];
+}
+
+export interface ListNotesOptions {
+  /** undefined = all folders, null = notes without a folder. */
+  folderId?: string | null;
+  tag?: string;
+  /** Case-insensitive match against title, content and tags. */
+  query?: string;
+  limit?: number;
+}
+
+/** Titles default to the content's first line, cut to this many characters. */
+const TITLE_MAX = 80;
+
+function parseNoteRow(row: typeof sessionMemory.$inferSelect): Note {
+  return {
+    id: row.id,
+    folderId: row.projectId ?? null,
+    type: row.type,
+    title: row.title,
+    content: row.content,
+    tags: safeJsonParse<string[]>(row.tags, []),
+    createdAt: row.createdAt,
+    updatedAt: row.updatedAt,
+  };
+}
+
+function defaultTitle(content: string): string {
+  const line = content.trim().split("\n")[0] ?? "";
+  return line.length > TITLE_MAX ? `${line.slice(0, TITLE_MAX - 3)}...` : line;
+}
+
+function matchesQuery(note: Note, query: string): boolean {
+  const q = query.toLowerCase();
+  return (
+    note.title.toLowerCase().includes(q) ||
+    note.content.toLowerCase().includes(q) ||
+    note.tags.some((t) => t.toLowerCase().includes(q))
+  );
+}
+
+/**
+ * List a user's notes, newest first.
+ */
+export async function listNotes(
+  userId: string,
+  options: ListNotesOptions = {}
+): Promise<Note[]> {
+  const conditions = [eq(sessionMemory.userId, userId)];
+  if (options.folderId !== undefined) {
+    conditions.push(
+      options.folderId === null
+        ? isNull(sessionMemory.projectId)
+        : eq(sessionMemory.projectId, options.folderId)
+    );
+  }
+
+  const rows = await db
+    .select()
+    .from(sessionMemory)
+    .where(and(...conditions))
+    .orderBy(desc(sessionMemory.createdAt));
+
+  let notes = rows.map(parseNoteRow);
+  if (options.tag) {
+    const tag = options.tag;
+    notes = notes.filter((n) => n.tags.includes(tag));
+  }
+  if (options.query) {
+    const query = options.query;
+    notes = notes.filter((n) => matchesQuery(n, query));
+  }
+  return options.limit ? notes.slice(0, options.limit) : notes;
+}
+
+/**
+ * Get a single note owned by the user.
+ */
+export async function getNote(id: string, userId: string): Promise<Note | null> {
+  const rows = await db
+    .select()
+    .from(sessionMemory)
+    .where(and(eq(sessionMemory.id, id), eq(sessionMemory.userId, userId)))
+    .limit(1);
+  return rows[0] ? parseNoteRow(rows[0]) : null;
+}
+
+/**
+ * Capture a note. A sessionId that doesn't belong to the user is ignored
+ * rather than leaking whether it exists.
+ */
+export async function createNote(userId: string, input: CreateNoteInput): Promise<Note> {
+  let folderId = input.folderId ?? null;
+  if (!folderId && input.sessionId) {
+    const session = await getSessionContext(input.sessionId);
+    if (session && session.userId === userId) {
+      folderId = session.projectId ?? null;
+    }
+  }
+
+  const now = new Date();
+  const [row] = await db
+    .insert(sessionMemory)
+    .values({
+      userId,
+      projectId: folderId,
+      type: input.type ?? "note",
+      title: input.title?.trim() || defaultTitle(input.content),
+      content: input.content,
+      tags: JSON.stringify(input.tags ?? []),
+      createdAt: now,
+      updatedAt: now,
+    })
+    .returning();
+  return parseNoteRow(row);
+}
+
+/**
+ * Update a note's title, content or tags. Tags are replaced, not merged.
+ */
+export async function updateNote(
+  id: string,
+  userId: string,
+  input: UpdateNoteInput
+): Promise<Note | null> {
+  const updates: Partial<typeof sessionMemory.$inferInsert> = { updatedAt: new Date() };
+  if (input.title !== undefined) updates.title = input.title;
+  if (input.content !== undefined) updates.content = input.content;
+  if (input.tags !== undefined) updates.tags = JSON.stringify(input.tags);
+
+  const [row] = await db
+    .update(sessionMemory)
+    .set(updates)
+    .where(and(eq(sessionMemory.id, id), eq(sessionMemory.userId, userId)))
+    .returning();
+  return row ? parseNoteRow(row) : null;
+}
+
+/**
+ * Delete a note. Returns false when it doesn't exist for this user.
+ */
+export async function deleteNote(id: string, userId: string): Promise<boolean> {
+  const rows = await db
+    .delete(sessionMemory)
+    .where(and(eq(sessionMemory.id, id), eq(sessionMemory.userId, userId)))
+    .returning({ id: sessionMemory.id });
+  return rows.length > 0;
+}
@@ -34,12 +34,17 @@ vi.mock("./tmux-service", () => ({
   sendKeys: vi.fn(async () => undefined),
 }));
 
+vi.mock("./automation-pause-service", () => ({
+  activePause: vi.fn(() => null),
+}));
+
 import {
   schedulerOrchestrator,
   classifyOneTimeRegistration,
   MISSED_FIRE_GRACE_MS,
 } from "./scheduler-orchestrator";
 import * as TmuxService from "./tmux-service";
+import * as AutomationPauseService from "./automation-pause-service";
 import {
   projects,
   scheduleCommands,
@@ -54,6 +59,7 @@ const USER = "orch-user-1";
 
 const mockedSendKeys = vi.mocked(TmuxService.sendKeys);
 const mockedSessionExists = vi.mocked(TmuxService.sessionExists);
+const mockedActivePause = vi.mocked(AutomationPauseService.activePause);
 
 async function seedSession(id: string, status: SessionStatus = "active"): Promise<void> {
   await handle.db.insert(terminalSessions).values({
@@ -164,6 +170,7 @@ describe("SchedulerOrchestrator registration", () => {
       .values({ id: "project-1", userId: USER, name: "Test Project" });
     mockedSendKeys.mockClear();
     mockedSessionExists.mockClear();
+    mockedActivePause.mockClear();
     mockedSessionExists.mockResolvedValue(true);
   });
 
@@ -316,6 +323,34 @@ describe("SchedulerOrchestrator registration", () => {
     expect(schedulerOrchestrator.getJobCount()).toBe(0);
   });
 
+  it("skips the fire without executing while automation is paused", async () => {
+    mockedActivePause.mockReturnValueOnce({
+      folderId: null,
+      folderName: null,
+      pausedAt: new Date().toISOString(),
+      until: null,
+      reason: "deploy freeze",
+    });
+    await seedSession("open-session");
+    await seedSchedule({
+      id: "paused-fire",
+      sessionId: "open-session",
+      scheduleType: "one-time",
+      scheduledAt: new Date(Date.now() - 60_000),
+    });
+
+    await schedulerOrchestrator.start();
+
+    await vi.waitFor(() => expect(mockedActivePause).toHaveBeenCalledWith(USER, "project-1"), {
+      timeout: 5000,
+    });
+    expect(mockedSendKeys).not.toHaveBeenCalled();
+    const executions = await handle.db.query.scheduleExecutions.findMany({
+      where: eq(scheduleExecutions.scheduleId, "paused-fire"),
+    });
+    expect(executions).toHaveLength(0);
+  });
+
   it("persists a fresh nextRunAt for recurring schedules whose stored value is stale", async () => {
     await seedSession("open-session");
     const staleNextRun = new Date(Date.now() - 3_600_000);
@@ -16,6 +16,7 @@ import { db } from "@/db";
 import { terminalSessions } from "@/db/schema";
 import { eq } from "drizzle-orm";
 import * as ScheduleService from "./schedule-service";
+import * as AutomationPauseService from "./automation-pause-service";
 import type { SessionScheduleWithCommands } from "@/types/schedule";
 import { createLogger } from "@/lib/logger";
 
@@ -377,6 +378,22 @@ class SchedulerOrchestrator {
         return;
       }
 
+      const [session] = await db
+        .select({ projectId: terminalSessions.projectId })
+        .from(terminalSessions)
+        .where(eq(terminalSessions.id, schedule.sessionId))
+        .limit(1);
+      const pause = AutomationPauseService.activePause(schedule.userId, session?.projectId);
+      if (pause) {
+        log.info("Automation paused; skipping schedule fire", {
+          scheduleId,
+          folderId: pause.folderId,
+          until: pause.until,
+          reason: pause.reason,
+        });
+        return;
+      }
+
       // Execute the schedule
       const execution = await ScheduleService.executeSchedule(
         schedule,
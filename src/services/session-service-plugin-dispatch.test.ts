@@ -44,6 +44,9 @@ const hoisted = vi.hoisted(() => {
     >(),
     claimPortsForSession: vi.fn(async () => undefined),
     releasePortsForSession: vi.fn(async () => undefined),
+    fetchSecretsForFolder: vi.fn<
+      (folderId: string, userId: string) => Promise<{ secrets: Record<string, string> } | null>
+    >(async () => null),
   };
 });
 
@@ -188,6 +191,10 @@ vi.mock("./port-claims-service", () => ({
   releasePortsForSession: hoisted.releasePortsForSession,
 }));
 
+vi.mock("./secrets-service", () => ({
+  fetchSecretsForFolder: hoisted.fetchSecretsForFolder,
+}));
+
 vi.mock("@/services/task-service", () => ({
   cancelOpenAgentTasks: vi.fn(async () => undefined),
 }));
@@ -404,6 +411,29 @@ describe("SessionService.createSession — plugin dispatch", () => {
     expect(shellCmd).toBe("fake-cli");
   });
 
+  it("injects the folder's secrets into the tmux session environment", async () => {
+    hoisted.fetchSecretsForFolder.mockResolvedValueOnce({
+      secrets: { DATABASE_URL: "postgres://secret" },
+    });
+    const plugin = makeFakePlugin("fake", {
+      useTmux: true,
+      shellCommand: "fake-cli",
+    });
+    TerminalTypeServerRegistry.register(plugin);
+    TerminalTypeServerRegistry.setDefaultType("fake");
+
+    await createSession("user-1", baseInput());
+
+    expect(hoisted.fetchSecretsForFolder).toHaveBeenCalledWith("project-1", "user-1");
+    const [, , , env] = tmuxCreate.mock.calls[0] as unknown as [
+      string,
+      string | undefined,
+      string | undefined,
+      Record<string, string> | undefined,
+    ];
+    expect(env?.DATABASE_URL).toBe("postgres://secret");
+  });
+
   it("does NOT thread any folder-level wrapper command into plugin input (regression for removed startupCommand mechanism)", async () => {
     // Even if a (hypothetical) future preference shape leaked a
     // `startupCommand` into the resolved prefs, the service must not
@@ -470,6 +500,26 @@ describe("SessionService.createSession — plugin dispatch", () => {
     // DB insert should NOT have been attempted
     expect(dbState.inserted).toHaveLength(0);
   });
+
+  it("throws QUOTA_EXCEEDED when the folder is at its open-session cap", async () => {
+    vi.stubEnv("RDV_MAX_SESSIONS_PER_FOLDER", "2");
+    TerminalTypeServerRegistry.register(makeFakePlugin("fake"));
+    TerminalTypeServerRegistry.setDefaultType("fake");
+    dbMocks.findManyTabOrder.mockResolvedValue([
+      makeDbRow({ id: "a" }),
+      makeDbRow({ id: "b" }),
+    ]);
+
+    try {
+      await expect(createSession("user-1", baseInput())).rejects.toHaveProperty(
+        "code",
+        "QUOTA_EXCEEDED"
+      );
+      expect(dbState.inserted).toHaveLength(0);
+    } finally {
+      vi.unstubAllEnvs();
+    }
+  });
 });
 
 describe("SessionService.createSession — scope-key dedup", () => {
@@ -131,6 +131,26 @@ async function resolveFolderGitIdentityEnv(
   }
 }
 
+/**
+ * Resolve the folder's secrets (from its configured provider) as env vars, so
+ * they reach the tmux session environment without any .env file on disk.
+ * Dynamic import keeps the provider SDKs out of this module's load path.
+ */
+async function resolveFolderSecretsEnv(
+  userId: string,
+  folderId: string | null | undefined
+): Promise<Record<string, string>> {
+  if (!folderId) return {};
+  try {
+    const { fetchSecretsForFolder } = await import("./secrets-service");
+    const result = await fetchSecretsForFolder(folderId, userId);
+    return result?.secrets ?? {};
+  } catch (error) {
+    log.error("Failed to resolve folder secrets", { folderId, error: String(error) });
+    return {};
+  }
+}
+
 /**
  * Result of createSession. `reused` is true when scope-key dedup returned
  * an existing row instead of creating a new one; the client uses this to
@@ -228,6 +248,26 @@ export async function createSessionWithDedupFlag(
     }
   }
 
+  // Per-folder cap on open sessions (RDV_MAX_SESSIONS_PER_FOLDER; unset or
+  // 0 means unlimited). Reused scope-keyed sessions above don't count as new.
+  const maxPerFolder = Number(process.env.RDV_MAX_SESSIONS_PER_FOLDER ?? 0);
+  if (maxPerFolder > 0) {
+    const open = await db.query.terminalSessions.findMany({
+      where: and(
+        eq(terminalSessions.userId, userId),
+        eq(terminalSessions.projectId, input.projectId),
+        inArray(terminalSessions.status, ["active", "suspended"])
+      ),
+      columns: { id: true },
+    });
+    if (open.length >= maxPerFolder) {
+      throw new SessionServiceError(
+        `Folder already has ${open.length} open sessions (limit ${maxPerFolder})`,
+        "QUOTA_EXCEEDED"
+      );
+    }
+  }
+
   const sessionId = crypto.randomUUID();
   const tmuxSessionName = TmuxService.generateSessionName(sessionId);
 
@@ -797,6 +837,7 @@ export async function createSessionWithDedupFlag(
   if (plugin.useTmux) {
     const gitCredentialEnv = await resolveGitCredentialEnv(sessionId, !!profile);
     const folderGitIdentityEnv = await resolveFolderGitIdentityEnv(userId, input.projectId);
+    const folderSecretsEnv = await resolveFolderSecretsEnv(userId, input.projectId);
 
     // Claude Code agent defaults (lowest precedence — overridable via profile/folder env).
     // Gated on isAgentRuntime so SSH sessions don't get CLAUDE_CODE_* env vars they
@@ -807,7 +848,8 @@ export async function createSessionWithDedupFlag(
 
     // Initial environment — all must be present at PTY spawn so agent processes inherit them immediately.
     // Precedence (low → high): claudeAgentDefaults < pluginEnv < profileEnv < proxyEnv < modelProxyEnv
-    //   < folderEnv < folderGitIdentityEnv < gitCredentialEnv < ghAccountEnv < rdvEnv.
+    //   < folderEnv < folderSecretsEnv < folderGitIdentityEnv < gitCredentialEnv < ghAccountEnv
+    //   < rdvEnv.
     const initialEnv: Record<string, string> = {
       ...claudeAgentDefaults,
       ...(sessionConfig.environment ?? {}),
@@ -815,6 +857,7 @@ export async function createSessionWithDedupFlag(
       ...proxyEnv,
       ...modelProxyEnv, // [aehq] proxy token + base URL win over LiteLLM (proxyEnv) + profile
       ...(folderEnv ?? {}),
+      ...folderSecretsEnv,
       ...folderGitIdentityEnv,
       ...gitCredentialEnv,
       ...(ghAccountEnv ?? {}),
@@ -21,6 +21,8 @@ import {
 } from "@/db/schema";
 import { createLogger } from "@/lib/logger";
 import * as AgentRunService from "./agent-run-service";
+import * as AutomationPauseService from "./automation-pause-service";
+import type { Pause } from "./automation-pause-service";
 import type { GithubEvent } from "@/lib/github-webhook-auth";
 import type { TriggerKind } from "@/types/agent-run";
 
@@ -75,6 +77,8 @@ export interface TriggerDeps {
    * (claim won), `false` if the id already existed (redelivery / duplicate).
    */
   recordDelivery(deliveryId: string, eventKind: string): Promise<boolean>;
+  /** The pause blocking automation for this user/folder, if any. */
+  activePause(userId: string, projectId: string): Pause | null;
 }
 
 /** Per-kind event-shape predicates (kind ↔ GitHub event/action). */
@@ -166,6 +170,8 @@ function defaultDeps(): TriggerDeps {
         .returning({ deliveryId: webhookDeliveries.deliveryId });
       return inserted.length > 0;
     },
+    activePause: (userId, projectId) =>
+      AutomationPauseService.activePause(userId, projectId),
   };
 }
 
@@ -231,6 +237,18 @@ export async function handleEvent(
       continue;
     }
 
+    const pause = deps.activePause(cfg.userId, cfg.projectId);
+    if (pause) {
+      log.info("automation paused; trigger matched but not run", {
+        cfg: cfg.id,
+        folderId: pause.folderId,
+        until: pause.until,
+        reason: pause.reason,
+      });
+      await deps.record(cfg.id, e, true, null);
+      continue;
+    }
+
     try {
       const run = await deps.launchAgentRun({
         userId: cfg.userId,
@@ -0,0 +1,92 @@
+// @vitest-environment node
+/**
+ * Tests for WorktreeService.createBranchWithWorktree collision handling,
+ * against a real git repository in a temp dir: stale registrations are pruned
+ * first, taken names get a suffix, and `reuseExisting` only reuses a clean
+ * worktree on the requested branch.
+ */
+import { describe, it, expect, beforeEach, afterEach } from "vitest";
+import { execFileSync } from "node:child_process";
+import { mkdtempSync, rmSync, writeFileSync } from "node:fs";
+import { tmpdir } from "node:os";
+import { join } from "node:path";
+import { createBranchWithWorktree } from "./worktree-service";
+
+let tmpDir: string;
+let repo: string;
+
+function git(...args: string[]): string {
+  return execFileSync("git", ["-C", repo, ...args], { encoding: "utf8" }).trim();
+}
+
+beforeEach(() => {
+  tmpDir = mkdtempSync(join(tmpdir(), "worktree-service-"));
+  repo = join(tmpDir, "repo");
+  execFileSync("git", ["init", "-q", repo]);
+  git("-c", "user.email=t@example.com", "-c", "user.name=t", "commit", "-q", "--allow-empty", "-m", "init");
+});
+
+afterEach(() => {
+  rmSync(tmpDir, { recursive: true, force: true });
+});
+
+describe("createBranchWithWorktree", () => {
+  it("creates the branch at the default sibling path", async () => {
+    const result = await createBranchWithWorktree(repo, "feat");
+
+    expect(result).toEqual({
+      branch: "feat",
+      worktreePath: join(tmpDir, "repo-feat"),
+      reused: false,
+    });
+  });
+
+  it("prunes a stale registration that blocks the target path", async () => {
+    const target = join(tmpDir, "repo-feat");
+    git("worktree", "add", "-q", "-b", "old", target);
+    rmSync(target, { recursive: true, force: true });
+
+    const result = await createBranchWithWorktree(repo, "feat");
+
+    expect(result.worktreePath).toBe(target);
+    expect(result.branch).toBe("feat");
+  });
+
+  it("retries with a suffix when the branch is checked out elsewhere", async () => {
+    git("worktree", "add", "-q", "-b", "feat", join(tmpDir, "elsewhere"));
+
+    const result = await createBranchWithWorktree(repo, "feat");
+
+    expect(result).toEqual({
+      branch: "feat-2",
+      worktreePath: join(tmpDir, "repo-feat-2"),
+      reused: false,
+    });
+  });
+
+  it("reuses a clean worktree on the requested branch with reuseExisting", async () => {
+    await createBranchWithWorktree(repo, "feat");
+
+    const result = await createBranchWithWorktree(repo, "feat", undefined, undefined, {
+      reuseExisting: true,
+    });
+
+    expect(result).toEqual({
+      branch: "feat",
+      worktreePath: join(tmpDir, "repo-feat"),
+      reused: true,
+    });
+  });
+
+  it("does not reuse a dirty worktree with reuseExisting", async () => {
+    await createBranchWithWorktree(repo, "feat");
+    writeFileSync(join(tmpDir, "repo-feat", "scratch.txt"), "wip");
+
+    const result = await createBranchWithWorktree(repo, "feat", undefined, undefined, {
+      reuseExisting: true,
+    });
+
+    expect(result.reused).toBe(false);
+    expect(result.branch).toBe("feat-2");
+  });
+});
@@ -374,15 +374,35 @@ export async function pruneWorktrees(repoPath: string): Promise<void> {
   }
 }
 
+/**
+ * How many suffixed names (`-2`, `-3`, ...) to try when the requested branch or
+ * path is taken, e.g. by a parallel session creation.
+ */
+const MAX_SUFFIX_ATTEMPTS = 5;
+
+export interface CreateBranchWithWorktreeOptions {
+  /**
+   * Only reuse a worktree already at the target path when it is clean and on
+   * the requested branch; anything else gets a suffixed branch and path.
+   * Without this, any git checkout at the target path is reused (retry case).
+   */
+  reuseExisting?: boolean;
+}
+
 /**
  * Create a new branch and worktree for it
+ *
+ * Stale worktree registrations (directory deleted) are pruned first. When the
+ * branch is checked out elsewhere or the path is taken by something that
+ * can't be reused, retries with a suffixed branch and path.
  */
 export async function createBranchWithWorktree(
   repoPath: string,
   branchName: string,
   baseBranch?: string,
-  worktreePath?: string
-): Promise<{ branch: string; worktreePath: string }> {
+  worktreePath?: string,
+  options: CreateBranchWithWorktreeOptions = {}
+): Promise<{ branch: string; worktreePath: string; reused: boolean }> {
   // Validate repo path first
   if (!(await isGitRepo(repoPath))) {
     throw new WorktreeServiceError(
@@ -395,8 +415,12 @@ export async function createBranchWithWorktree(
   // Fetch remote refs to ensure we have the latest
   await fetchRemoteRefs(repoPath);
 
-  // Create worktree with new branch based on the base branch
-  // Build args to specify the start point for the new branch
+  // A registration whose directory is gone blocks `worktree add` on that path.
+  const worktrees = await listWorktrees(repoPath);
+  if (worktrees.some((wt) => wt.isPrunable)) {
+    await execFileNoThrow("git", ["-C", repoPath, "worktree", "prune"]);
+  }
+
   const targetPath =
     worktreePath ||
     runtimeJoin(runtimeDirname(repoPath), `${runtimeBasename(repoPath)}-${sanitizeBranchName(branchName)}`);
@@ -423,8 +447,8 @@ export async function createBranchWithWorktree(
     }
   }
 
-  // Create worktree with new branch: git worktree add -b <branch> <path> [<start-point>]
-  const args = ["-C", repoPath, "worktree", "add", "-b", branchName, targetPath];
+  // Build args to specify the start point for the new branch
+  let startPoint: string | undefined;
   if (baseBranch) {
     // Try origin/baseBranch first, fall back to just baseBranch
     const remoteRef = `origin/${baseBranch}`;
@@ -436,7 +460,7 @@ export async function createBranchWithWorktree(
       remoteRef,
     ]);
     if (checkRemote.exitCode === 0) {
-      args.push(remoteRef);
+      startPoint = remoteRef;
     } else {
       // Try local branch
       const checkLocal = await execFileNoThrow("git", [
@@ -447,17 +471,68 @@ export async function createBranchWithWorktree(
         baseBranch,
       ]);
       if (checkLocal.exitCode === 0) {
-        args.push(baseBranch);
+        startPoint = baseBranch;
       }
       // If neither exists, let git use HEAD (no start-point specified)
     }
   }
 
+  for (let attempt = 1; ; attempt++) {
+    const branch = attempt === 1 ? branchName : `${branchName}-${attempt}`;
+    const path = attempt === 1 ? targetPath : `${targetPath}-${attempt}`;
+    try {
+      return await addBranchWorktree(repoPath, branch, path, startPoint, options);
+    } catch (error) {
+      const retryable =
+        error instanceof WorktreeServiceError &&
+        (error.code === "BRANCH_IN_USE" || error.code === "PATH_EXISTS");
+      if (!retryable || attempt >= MAX_SUFFIX_ATTEMPTS) throw error;
+      log.info("Worktree name taken, retrying with suffix", {
+        branch,
+        path,
+        code: error.code,
+      });
+    }
+  }
+}
+
+/**
+ * Whether the checkout at `path` can stand in for a new worktree on `branchName`.
+ */
+async function canReuseWorktree(
+  path: string,
+  branchName: string,
+  requireClean: boolean
+): Promise<boolean> {
+  const fs = await getFs();
+  if (!fs.existsSync(path) || !(await isGitRepo(path))) return false;
+  if (!requireClean) return true;
+  return (
+    (await getCurrentBranch(path)) === branchName &&
+    !(await hasUncommittedChanges(path))
+  );
+}
+
+/**
+ * One `git worktree add` attempt for createBranchWithWorktree.
+ */
+async function addBranchWorktree(
+  repoPath: string,
+  branchName: string,
+  targetPath: string,
+  startPoint: string | undefined,
+  options: CreateBranchWithWorktreeOptions
+): Promise<{ branch: string; worktreePath: string; reused: boolean }> {
+  // Create worktree with new branch: git worktree add -b <branch> <path> [<start-point>]
+  const args = ["-C", repoPath, "worktree", "add", "-b", branchName, targetPath];
+  if (startPoint) args.push(startPoint);
+
   try {
     await execFile("git", args);
     return {
       branch: branchName,
       worktreePath: targetPath,
+      reused: false,
     };
   } catch (error) {
     const err = error as Error & { stderr?: string };
@@ -466,8 +541,8 @@ export async function createBranchWithWorktree(
     // If the target path already exists as a valid git worktree, reuse it.
     // This handles retries where a previous attempt created the worktree
     // but session creation failed afterwards (e.g., "Start Working" on an issue).
-    if (fs.existsSync(targetPath) && await isGitRepo(targetPath)) {
-      return { branch: branchName, worktreePath: targetPath };
+    if (await canReuseWorktree(targetPath, branchName, !!options.reuseExisting)) {
+      return { branch: branchName, worktreePath: targetPath, reused: true };
     }
 
     // "a branch named 'X' already exists" — branch exists from a previous
@@ -479,11 +554,25 @@ export async function createBranchWithWorktree(
       try {
         const retryArgs = ["-C", repoPath, "worktree", "add", targetPath, branchName];
         await execFile("git", retryArgs);
-        return { branch: branchName, worktreePath: targetPath };
+        return { branch: branchName, worktreePath: targetPath, reused: false };
       } catch (retryError) {
         const retryErr = retryError as Error & { stderr?: string };
         const retryStderr = retryErr.stderr || retryErr.message;
         log.error("Retry failed for branch", { branchName, error: retryStderr });
+        if (retryStderr.includes("already checked out") || retryStderr.includes("already used by worktree")) {
+          throw new WorktreeServiceError(
+            "Branch is already checked out in another worktree",
+            "BRANCH_IN_USE",
+            branchName
+          );
+        }
+        if (retryStderr.includes("already exists")) {
+          throw new WorktreeServiceError(
+            "Worktree path already exists",
+            "PATH_EXISTS",
+            targetPath
+          );
+        }
         throw new WorktreeServiceError(
           `Failed to create worktree with existing branch: ${retryStderr}`,
           "CREATE_FAILED",
@@ -492,7 +581,7 @@ export async function createBranchWithWorktree(
       }
     }
 
-    if (stderr.includes("already checked out")) {
+    if (stderr.includes("already checked out") || stderr.includes("already used by worktree")) {
       throw new WorktreeServiceError(
         "Branch is already checked out in another worktree",
         "BRANCH_IN_USE",